@@ -1,7 +1,42 @@
-use crate::convert::{InputConverterType, OutputConverterType};
+use crate::{
+    convert::{InputConverterType, OutputConverterType},
+    opts::Layout,
+    ui::ColorMode,
+};
 
 pub const CONFIG_FILE_PATH: &str = ".config/baseic/config.toml";
 
+// Input value that makes baseic read values from stdin, one per line
+pub const STDIN_INPUT: &str = "-";
+
+// Prefix forcing the rest of a token to be read as a literal value
+pub const LITERAL_PREFIX: char = '=';
+
+// Terminal width assumed when stdout is not a terminal
+pub const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+// Spaces between grid columns
+pub const GRID_COLUMN_GAP: usize = 2;
+
+// Bits drawn in each row of a bit diagram
+pub const DIAGRAM_ROW_BITS: u32 = 32;
+
+// Output converter name taking the name of a register declared in the config file
+pub const REGISTER_CONVERTER: &str = "reg";
+
+// Converter name taking the name of a flag set
+pub const FLAGS_CONVERTER: &str = "flags";
+
+// Output converter name taking the name of an enumeration declared in the config file
+pub const ENUM_CONVERTER: &str = "enum";
+
+// Command starting a quiz session instead of a conversion
+pub const QUIZ_COMMAND: &str = "quiz";
+
+// Width in bits of quiz values, and number of questions, when not specified
+pub const DEFAULT_QUIZ_WIDTH: u32 = 8;
+pub const DEFAULT_QUIZ_COUNT: usize = 10;
+
 // Configuration deafults
 pub fn default_outconvs() -> Vec<OutputConverterType> {
     vec![
@@ -20,5 +55,14 @@ pub fn default_inconvs() -> Vec<InputConverterType> {
         InputConverterType::BIN,
         InputConverterType::OCT,
         InputConverterType::ASCII,
+        InputConverterType::SYMBOL,
     ]
 }
+
+pub fn default_layout() -> Layout {
+    Layout::List
+}
+
+pub fn default_color() -> ColorMode {
+    ColorMode::Auto
+}
@@ -1,29 +1,163 @@
 mod ascii;
+mod bcd;
 mod bin;
+mod bits;
+mod cast;
+mod codes;
 mod conv_types;
 mod dec;
+mod flags;
+mod gray;
 mod hex;
 mod octal;
+mod ops;
+mod register;
+mod slice;
+mod symbols;
+mod unary;
 
 use indexmap::{IndexMap, IndexSet};
+use nom::error::{Error, ErrorKind};
+use std::io;
 
-use crate::Opts;
+use crate::{explain::Explanation, template::Template, Opts};
 
+use bits::{fits_width, mask};
+use slice::read_input;
+
+pub use bits::fitting_width;
 pub use conv_types::{InputConverterType, OutputConverterType};
+pub use flags::{flag_sets, set_flag_sets, FlagSets};
+pub use ops::BitOperation;
+pub use register::{registers, set_registers, Registers, SvdError};
+pub use slice::BitSlice;
+pub use symbols::{enums, set_constants, set_enums, Constants, Enums};
 
 /// Intermediate type used for conversions
-type IntermediateValue = i128;
+pub type IntermediateValue = i128;
 
 /// Represents a generic output converter
 pub trait OutputConverter {
     /// Convert value to the intermediate type
-    fn convert(&self, input: IntermediateValue) -> Result<String, ()>;
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError>;
+
+    /// Explain step by step how a value is converted
+    ///
+    /// None for numeric bases, explained from the digits of the input value instead
+    fn explain(&self, _input: IntermediateValue) -> Option<Vec<String>> {
+        None
+    }
+}
+
+/// Reason why an output converter skipped a value
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OutputError {
+    /// Negative values have no unsigned representation
+    Negative,
+    /// Value outside of the printable ASCII characters
+    NotPrintable,
+    /// Value that cannot be written in the given width in bits
+    DoesNotFit(u32),
+    /// Value without a meaning in a table of well-known codes
+    UnknownCode,
+    /// Value not named in the enumeration
+    NotInEnum,
+    /// Register without any field to decode
+    NoFields,
 }
 
 /// Represents a generic input converter
 pub trait InputConverter {
     /// Convert value to the intermediate type
-    fn convert(&self, input: &str) -> Result<IntermediateValue, ()>;
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError>;
+}
+
+/// Reason why an input converter rejected a value
+#[derive(Debug, PartialEq)]
+pub enum InputError {
+    /// Nothing to convert
+    Empty,
+    /// Prefix or sign not followed by any digit
+    EmptyAfterPrefix(String),
+    /// Character not allowed at a given column, counted from 0
+    InvalidCharacter(usize, char),
+    /// Value beyond the range of the intermediate type
+    Overflow,
+    /// Register field assignment without a value
+    MissingFieldValue(String),
+    /// Field not in the register
+    UnknownField(String),
+    /// Value neither a number fitting the field nor one of its names
+    InvalidFieldValue(String, String),
+    /// Term neither a flag of the set nor a number
+    UnknownFlag(String),
+    /// Name not found in any table of well-known codes
+    UnknownName(String),
+    /// Name neither a constant nor a value of an enumeration
+    UnknownSymbol(String),
+    /// Bit range not written as high:low or as a single bit
+    InvalidBitRange(String),
+    /// Value neither a number nor fitting a bit range of the given width
+    InvalidRangeValue(String, u32),
+    /// Value that cannot be written in the given width in bits, for an operation
+    DoesNotFit(u32),
+    /// Width in bits not made of whole bytes, for a byte swap
+    NotWholeBytes(u32),
+    /// No bit set in a one-hot code
+    NoBitSet,
+}
+
+impl InputError {
+    /// Column of the offending character, counted from 0
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            Self::EmptyAfterPrefix(prefix) => Some(prefix.chars().count()),
+            Self::InvalidCharacter(column, _) => Some(*column),
+            Self::Empty
+            | Self::Overflow
+            | Self::MissingFieldValue(_)
+            | Self::UnknownField(_)
+            | Self::InvalidFieldValue(..)
+            | Self::UnknownFlag(_)
+            | Self::UnknownName(_)
+            | Self::UnknownSymbol(_)
+            | Self::InvalidBitRange(_)
+            | Self::InvalidRangeValue(..)
+            | Self::DoesNotFit(_)
+            | Self::NotWholeBytes(_)
+            | Self::NoBitSet => None,
+        }
+    }
+
+    /// Build error from a failed nom parser, given the whole input
+    ///
+    /// A failing map_res is taken as an overflow of the parsed digits
+    fn from_nom(input: &str, err: nom::Err<Error<&str>>) -> Self {
+        let err = match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => err,
+            nom::Err::Incomplete(_) => return Self::Empty,
+        };
+        let consumed = &input[..input.len() - err.input.len()];
+
+        if err.code == ErrorKind::MapRes {
+            Self::Overflow
+        } else if let Some(c) = err.input.chars().next() {
+            Self::InvalidCharacter(consumed.chars().count(), c)
+        } else if consumed.is_empty() {
+            Self::Empty
+        } else {
+            Self::EmptyAfterPrefix(consumed.to_string())
+        }
+    }
+
+    /// Build error for characters left unconsumed by a parser
+    fn trailing(input: &str, rest: &str) -> Self {
+        let consumed = &input[..input.len() - rest.len()];
+        match rest.chars().next() {
+            Some(c) => Self::InvalidCharacter(consumed.chars().count(), c),
+            None => Self::Empty,
+        }
+    }
 }
 
 /// Represents the result of conversion
@@ -37,6 +171,11 @@ impl ConversionOutput {
     pub fn is_empty(&self) -> bool {
         self.inner.is_empty()
     }
+
+    /// Checks if there is any converted value, not counting skipped output converters
+    pub fn has_values(&self) -> bool {
+        self.inner.values().any(|res| !res.inner.is_empty())
+    }
 }
 
 impl From<IndexMap<InputConverterType, ConversionResult>> for ConversionOutput {
@@ -61,18 +200,23 @@ impl FromIterator<(InputConverterType, ConversionResult)> for ConversionOutput {
 #[derive(Debug)]
 pub struct ConversionResult {
     pub inner: IndexMap<OutputConverterType, String>,
+    /// Output converters that could not convert the value, with the reason
+    pub skipped: IndexMap<OutputConverterType, OutputError>,
 }
 
 impl ConversionResult {
-    /// Checks if there are any results
+    /// Checks if there are neither results nor skipped output converters
     pub fn is_empty(&self) -> bool {
-        self.inner.is_empty()
+        self.inner.is_empty() && self.skipped.is_empty()
     }
 }
 
 impl From<IndexMap<OutputConverterType, String>> for ConversionResult {
     fn from(value: IndexMap<OutputConverterType, String>) -> Self {
-        Self { inner: value }
+        Self {
+            inner: value,
+            skipped: IndexMap::new(),
+        }
     }
 }
 
@@ -88,60 +232,705 @@ impl FromIterator<(OutputConverterType, String)> for ConversionResult {
     }
 }
 
+/// Represents the results of converting several input values, laid out as a table
+#[derive(Debug)]
+pub struct ConversionTable {
+    /// Input and output converter pair shown in each column
+    pub columns: Vec<(InputConverterType, OutputConverterType)>,
+    /// Input value and conversion output shown in each row
+    pub rows: Vec<(String, ConversionOutput)>,
+}
+
+/// Input value that no input converter could interpret
+#[derive(Debug)]
+pub struct Rejections {
+    pub input: String,
+    /// Reason given by each input converter, empty if all of them accepted the value
+    pub inner: Vec<(InputConverterType, InputError)>,
+    /// Output converters that could not convert the value read by an input converter
+    pub skipped: Vec<(InputConverterType, OutputConverterType, OutputError)>,
+    /// Pairs of converters left out for an input converter that accepted the value
+    pub omitted: Vec<(InputConverterType, OutputConverterType)>,
+    /// Input converter that was not tried but would read the value
+    pub hint: Option<InputConverterType>,
+}
+
+/// Rendered format templates of a single input value
+#[derive(Debug)]
+pub struct TemplateOutput {
+    /// One rendered template per valid interpretation of the input
+    pub lines: Vec<String>,
+    /// Interpretations for which an output converter of the template failed
+    pub skipped: Vec<(InputConverterType, OutputConverterType, OutputError)>,
+}
+
+/// Definitions of the config file set after being used, which has no effect
+#[derive(Debug, PartialEq)]
+pub struct AlreadyInUse(pub &'static str);
+
 /// Conversion error
 #[derive(Debug)]
 pub enum ConversionError {
-    NoResults,
+    NoResults(Rejections),
+    ReadInput(io::Error),
+    AmbiguousRawOutput(usize),
+    SkippedRawOutput(InputConverterType, OutputConverterType, OutputError),
+    NoQuizBases,
+}
+
+impl ConversionError {
+    /// Process exit code to report this error with
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::NoResults(_)
+            | Self::ReadInput(_)
+            | Self::SkippedRawOutput(..)
+            | Self::NoQuizBases => 2,
+            Self::AmbiguousRawOutput(_) => 3,
+        }
+    }
+}
+
+/// Bits of an interpretation of the input value, to be drawn as a diagram
+#[derive(Debug, PartialEq)]
+pub struct BitDiagram {
+    pub inconv: InputConverterType,
+    /// Width in bits of the diagram
+    pub width: u32,
+    /// Bits of the value, in two's complement if negative, or why they don't fit the width
+    pub bits: Result<u128, OutputError>,
+}
+
+impl BitDiagram {
+    /// Take the bits of a value, on the given width or the smallest that fits it
+    pub fn new(inconv: InputConverterType, value: IntermediateValue, width: Option<u32>) -> Self {
+        let width = width.unwrap_or_else(|| fitting_width(value));
+        let bits = if fits_width(value, width) {
+            Ok(value as u128 & mask(width))
+        } else {
+            Err(OutputError::DoesNotFit(width))
+        };
+
+        Self {
+            inconv,
+            width,
+            bits,
+        }
+    }
+}
+
+/// Performs conversion of a single input value with given options
+///
+/// Skipped output converters alone are no results
+pub fn do_convert(input: &str, opts: &Opts) -> Result<ConversionOutput, ConversionError> {
+    let res = process_inconvs(input, opts);
+
+    // Check if there is any value
+    if res.has_values() {
+        Ok(res)
+    } else {
+        Err(ConversionError::NoResults(rejections(input, opts)))
+    }
+}
+
+/// Performs conversion of a single input value, expecting exactly one result
+///
+/// Returns the bare converted value
+pub fn do_convert_raw(input: &str, opts: &Opts) -> Result<String, ConversionError> {
+    let res = process_inconvs(input, opts);
+    if res.is_empty() {
+        return Err(ConversionError::NoResults(rejections(input, opts)));
+    }
+
+    // Without any value, report why the output converter was skipped
+    let skipped = res.inner.iter().find_map(|(inconv, res)| {
+        let (outconv, err) = res.skipped.first()?;
+        Some(ConversionError::SkippedRawOutput(*inconv, *outconv, *err))
+    });
+
+    // Refuse to pick a value when there is more than one
+    let mut values = res
+        .inner
+        .into_values()
+        .flat_map(|res| res.inner.into_values());
+    match (values.next(), values.count(), skipped) {
+        (Some(value), 0, _) => Ok(value),
+        (None, _, Some(err)) => Err(err),
+        (_, rest, _) => Err(ConversionError::AmbiguousRawOutput(rest + 1)),
+    }
+}
+
+/// Performs conversion of a single input value, rendering a format template
+///
+/// Returns one rendered template per valid interpretation of the input, and why the others
+/// could not be rendered
+pub fn do_convert_template(
+    input: &str,
+    opts: &Opts,
+    template: &Template,
+) -> Result<TemplateOutput, ConversionError> {
+    let mut lines = Vec::new();
+    let mut skipped = Vec::new();
+    for inconv in plausible_inconvs(input, opts) {
+        let Ok(int) = read_value(inconv, input, opts) else {
+            continue;
+        };
+        match template.render(int, opts.width) {
+            Ok(line) => lines.push(line),
+            Err((outconv, err)) => skipped.push((*inconv, outconv, err)),
+        }
+    }
+
+    // Check if result is empty, only the template's output converters count
+    if lines.is_empty() {
+        return Err(ConversionError::NoResults(Rejections {
+            skipped,
+            omitted: Vec::new(),
+            ..rejections(input, opts)
+        }));
+    }
+    Ok(TemplateOutput { lines, skipped })
+}
+
+/// Performs conversion of a single input value, explaining how each result is derived
+///
+/// Returns one explanation per valid interpretation of the input
+pub fn do_convert_explain(input: &str, opts: &Opts) -> Result<Vec<Explanation>, ConversionError> {
+    let res: Vec<Explanation> = plausible_inconvs(input, opts)
+        .into_iter()
+        .filter_map(|inconv| {
+            // Operations are explained one by one, starting from the value as read
+            read_value(inconv, input, opts).ok()?;
+            let int = read_input(inconv, input).ok()?;
+            Some(Explanation::new(
+                *inconv,
+                &opts.outconvs,
+                input,
+                int,
+                &opts.ops,
+                opts.width,
+            ))
+        })
+        .collect();
+
+    // Check if result is empty
+    if res.is_empty() {
+        Err(ConversionError::NoResults(rejections(input, opts)))
+    } else {
+        Ok(res)
+    }
+}
+
+/// Performs conversion of a single input value, drawing its bits
+///
+/// Returns one bit diagram per valid interpretation of the input
+pub fn do_convert_diagram(input: &str, opts: &Opts) -> Result<Vec<BitDiagram>, ConversionError> {
+    let res: Vec<BitDiagram> = plausible_inconvs(input, opts)
+        .into_iter()
+        .filter_map(|inconv| {
+            let int = read_value(inconv, input, opts).ok()?;
+            Some(BitDiagram::new(*inconv, int, opts.width))
+        })
+        .collect();
+
+    // Check if result is empty
+    if res.is_empty() {
+        Err(ConversionError::NoResults(rejections(input, opts)))
+    } else {
+        Ok(res)
+    }
+}
+
+/// Collect why an input value without results was rejected, or why its outputs were left out
+fn rejections(input: &str, opts: &Opts) -> Rejections {
+    let mut inner = Vec::new();
+    let mut skipped = Vec::new();
+    let mut omitted = Vec::new();
+    for inconv in plausible_inconvs(input, opts) {
+        let int = match read_value(inconv, input, opts) {
+            Ok(int) => int,
+            Err(err) => {
+                inner.push((*inconv, err));
+                continue;
+            }
+        };
+
+        let excluded = excluded_inconv(inconv, input, opts);
+        let res = proces_outconvs(&opts.outconvs, excluded, int, opts.width);
+        skipped.extend(
+            res.skipped
+                .into_iter()
+                .map(|(outconv, err)| (*inconv, outconv, err)),
+        );
+        omitted.extend(
+            opts.outconvs
+                .iter()
+                .filter(|outconv| {
+                    excluded.is_some_and(|inconv| inconv.is_outconv_excluded(outconv))
+                })
+                .map(|outconv| (*inconv, *outconv)),
+        );
+    }
+
+    // Code names are only read when asked for, point to the converter if it would help
+    let hint = (skipped.is_empty() && omitted.is_empty())
+        .then_some(InputConverterType::CODE)
+        .filter(|inconv| !opts.inconvs.contains(inconv) && read_input(inconv, input).is_ok());
+
+    Rejections {
+        input: input.to_string(),
+        inner,
+        skipped,
+        omitted,
+        hint,
+    }
+}
+
+/// Get the input converter whose paired output converter is left out, if any
+///
+/// A bit range or an operation changes the value, so converting back to the same base is not
+/// trivial
+fn excluded_inconv<'a>(
+    inconv: &'a InputConverterType,
+    input: &str,
+    opts: &Opts,
+) -> Option<&'a InputConverterType> {
+    (BitSlice::split(input).is_none() && opts.ops.is_empty()).then_some(inconv)
+}
+
+/// Performs conversion of multiple input values, collecting the results in a table
+///
+/// Values for which no conversion is possible produce empty rows
+pub fn do_convert_table(inputs: &[String], opts: &Opts) -> ConversionTable {
+    let rows: Vec<(String, ConversionOutput)> = inputs
+        .iter()
+        .map(|input| (input.clone(), process_inconvs(input, opts)))
+        .collect();
+
+    // Each pair of converters that gave a result for some value gets its own column
+    let columns = opts
+        .inconvs
+        .iter()
+        .flat_map(|inconv| opts.outconvs.iter().map(|outconv| (*inconv, *outconv)))
+        .filter(|(inconv, outconv)| {
+            rows.iter().any(|(_, output)| {
+                output
+                    .inner
+                    .get(inconv)
+                    .is_some_and(|res| res.inner.contains_key(outconv))
+            })
+        })
+        .collect();
+
+    ConversionTable { columns, rows }
+}
+
+/// Read an input value with an input converter, then apply the selected operations
+fn read_value(
+    inconv: &InputConverterType,
+    input: &str,
+    opts: &Opts,
+) -> Result<IntermediateValue, InputError> {
+    opts.ops
+        .iter()
+        .try_fold(read_input(inconv, input)?, |value, op| {
+            op.apply(value, opts.width)
+        })
 }
 
-/// Performs conversion with given options
-pub fn do_convert(opts: Opts) -> Result<ConversionOutput, ConversionError> {
-    let res: ConversionOutput = opts
+/// Select the input converters giving a plausible interpretation of an input value
+///
+/// A base prefix or suffix is authoritative, while binary and octal readings of a valid
+/// decimal number are considered unlikely. Nothing is filtered out when showing all,
+/// except symbols when none is declared.
+fn plausible_inconvs<'a>(input: &str, opts: &'a Opts) -> Vec<&'a InputConverterType> {
+    let has_symbols = symbols::has_symbols();
+    let inconvs = opts
         .inconvs
+        .iter()
+        .filter(|inconv| has_symbols || **inconv != InputConverterType::SYMBOL);
+    if opts.show_all {
+        return inconvs.collect();
+    }
+
+    // Prefix or suffix, only if the base it names is selected
+    if let Some(inconv) =
+        InputConverterType::from_notation(input).and_then(|inconv| opts.inconvs.get(&inconv))
+    {
+        return vec![inconv];
+    }
+
+    let is_decimal = opts.inconvs.contains(&InputConverterType::DEC)
+        && read_input(&InputConverterType::DEC, input).is_ok();
+    inconvs
+        .filter(|inconv| !(is_decimal && inconv.is_unlikely_besides_decimal()))
+        .collect()
+}
+
+/// Run all plausible input converters on a given input value
+fn process_inconvs(input: &str, opts: &Opts) -> ConversionOutput {
+    plausible_inconvs(input, opts)
         .into_iter()
         .filter_map(|inconv| {
             // Run input converter
-            if let Ok(int) = inconv.get_converter().convert(&opts.input) {
-                let res = proces_outconvs(&opts.outconvs, &inconv, int);
+            if let Ok(int) = read_value(inconv, input, opts) {
+                let excluded = excluded_inconv(inconv, input, opts);
+                let res = proces_outconvs(&opts.outconvs, excluded, int, opts.width);
                 if res.is_empty() {
                     None
                 } else {
-                    Some((inconv, res))
+                    Some((*inconv, res))
                 }
             } else {
                 None
             }
         })
-        .collect();
-
-    // Check if result is empty
-    if res.is_empty() {
-        Err(ConversionError::NoResults)
-    } else {
-        Ok(res)
-    }
+        .collect()
 }
 
 /// Process output converters from a given intermediate value
+///
+/// Output converters excluded by the input converter, if given, are not run
 pub fn proces_outconvs(
     outconvs: &IndexSet<OutputConverterType>,
-    inconv: &InputConverterType,
+    inconv: Option<&InputConverterType>,
     input: IntermediateValue,
+    width: Option<u32>,
 ) -> ConversionResult {
+    let mut res = ConversionResult::from(IndexMap::new());
+
     // Run selected output converters
-    outconvs
-        .into_iter()
-        .filter_map(|outconv| {
-            // Check if this output converter is excluded
-            if inconv.is_outconv_excluded(outconv) {
-                return None;
+    for outconv in outconvs {
+        // Check if this output converter is excluded
+        if inconv.is_some_and(|inconv| inconv.is_outconv_excluded(outconv)) {
+            continue;
+        }
+
+        match outconv.get_converter(width).convert(input) {
+            Ok(out) => {
+                res.inner.insert(*outconv, out);
+            }
+            Err(err) => {
+                res.skipped.insert(*outconv, err);
             }
+        }
+    }
 
-            if let Ok(out) = outconv.get_converter().convert(input) {
-                Some((outconv.clone(), out))
-            } else {
-                None
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::{indexmap, indexset};
+
+    use super::*;
+
+    #[test]
+    fn plausible_inconvs_ok() {
+        let all = indexset![
+            InputConverterType::DEC,
+            InputConverterType::HEX,
+            InputConverterType::BIN,
+            InputConverterType::OCT,
+            InputConverterType::ASCII,
+        ];
+        let tests = [
+            (
+                "10",
+                all.clone(),
+                vec![
+                    InputConverterType::DEC,
+                    InputConverterType::HEX,
+                    InputConverterType::ASCII,
+                ],
+            ),
+            ("0b11", all.clone(), vec![InputConverterType::BIN]),
+            ("0x10", all.clone(), vec![InputConverterType::HEX]),
+            ("11h", all.clone(), vec![InputConverterType::HEX]),
+            ("0bad", all.clone(), all.iter().copied().collect()),
+            (
+                "0b11",
+                indexset![InputConverterType::DEC, InputConverterType::HEX],
+                vec![InputConverterType::DEC, InputConverterType::HEX],
+            ),
+            (
+                "10",
+                indexset![InputConverterType::HEX, InputConverterType::BIN],
+                vec![InputConverterType::HEX, InputConverterType::BIN],
+            ),
+            ("a", all.clone(), all.iter().copied().collect()),
+        ];
+        for (input, inconvs, exp) in tests {
+            let opts = Opts {
+                inconvs,
+                outconvs: indexset![],
+                ..Default::default()
+            };
+            let res: Vec<_> = plausible_inconvs(input, &opts)
+                .into_iter()
+                .copied()
+                .collect();
+            assert_eq!(res, exp);
+        }
+
+        let opts = Opts {
+            show_all: true,
+            ..Opts {
+                inconvs: all.clone(),
+                outconvs: indexset![],
+                ..Default::default()
             }
-        })
-        .collect()
+        };
+        assert_eq!(plausible_inconvs("0b11", &opts).len(), all.len());
+    }
+
+    #[test]
+    fn do_convert_raw_ok() {
+        let opts = Opts {
+            inconvs: indexset![InputConverterType::DEC],
+            outconvs: indexset![OutputConverterType::HEX],
+            ..Default::default()
+        };
+        assert_eq!(do_convert_raw("255", &opts).unwrap(), "FF");
+
+        let opts = Opts {
+            inconvs: indexset![InputConverterType::HEX],
+            outconvs: indexset![OutputConverterType::DEC],
+            ops: vec![BitOperation::SignExtend(8)],
+            ..opts
+        };
+        assert_eq!(do_convert_raw("0x80", &opts).unwrap(), "-128");
+    }
+
+    #[test]
+    fn do_convert_raw_err() {
+        let dec_opts = Opts {
+            inconvs: indexset![InputConverterType::DEC],
+            outconvs: indexset![OutputConverterType::HEX],
+            ..Default::default()
+        };
+        assert!(matches!(
+            do_convert_raw("-1", &dec_opts),
+            Err(ConversionError::SkippedRawOutput(
+                InputConverterType::DEC,
+                OutputConverterType::HEX,
+                OutputError::Negative
+            ))
+        ));
+
+        let opts = Opts {
+            inconvs: indexset![InputConverterType::DEC, InputConverterType::HEX],
+            outconvs: indexset![OutputConverterType::BIN],
+            ..Default::default()
+        };
+        assert!(matches!(
+            do_convert_raw("255", &opts),
+            Err(ConversionError::AmbiguousRawOutput(2))
+        ));
+        assert!(matches!(
+            do_convert_raw("zz", &opts),
+            Err(ConversionError::NoResults(_))
+        ));
+    }
+
+    #[test]
+    fn proces_outconvs_ok() {
+        let outconvs = indexset![
+            OutputConverterType::DEC,
+            OutputConverterType::HEX,
+            OutputConverterType::ASCII,
+            OutputConverterType::BITS,
+        ];
+        let res = proces_outconvs(&outconvs, Some(&InputConverterType::DEC), -1, Some(4));
+        assert_eq!(res.inner.len(), 1);
+        assert_eq!(
+            res.skipped,
+            indexmap! {
+                OutputConverterType::HEX => OutputError::Negative,
+                OutputConverterType::ASCII => OutputError::NotPrintable,
+            }
+        );
+
+        let res = proces_outconvs(&outconvs, Some(&InputConverterType::DEC), 16, Some(4));
+        assert_eq!(
+            res.skipped.get(&OutputConverterType::BITS),
+            Some(&OutputError::DoesNotFit(4))
+        );
+    }
+
+    #[test]
+    fn bit_diagram_new_ok() {
+        let tests = [
+            (42, None, 8, Ok(42)),
+            (-1, None, 8, Ok(0xFF)),
+            (-1, Some(12), 12, Ok(0xFFF)),
+            (0x1234, None, 16, Ok(0x1234)),
+            (256, Some(8), 8, Err(OutputError::DoesNotFit(8))),
+        ];
+        for (value, width, exp_width, exp_bits) in tests {
+            let diagram = BitDiagram::new(InputConverterType::DEC, value, width);
+            assert_eq!(diagram.width, exp_width);
+            assert_eq!(diagram.bits, exp_bits);
+        }
+    }
+
+    #[test]
+    fn do_convert_err() {
+        let tests = [
+            (
+                "12x4",
+                vec![
+                    (
+                        InputConverterType::DEC,
+                        InputError::InvalidCharacter(2, 'x'),
+                    ),
+                    (
+                        InputConverterType::HEX,
+                        InputError::InvalidCharacter(2, 'x'),
+                    ),
+                    (
+                        InputConverterType::BIN,
+                        InputError::InvalidCharacter(1, '2'),
+                    ),
+                ],
+            ),
+            (
+                "0bz",
+                vec![
+                    (
+                        InputConverterType::DEC,
+                        InputError::InvalidCharacter(1, 'b'),
+                    ),
+                    (
+                        InputConverterType::HEX,
+                        InputError::InvalidCharacter(2, 'z'),
+                    ),
+                    (
+                        InputConverterType::BIN,
+                        InputError::InvalidCharacter(2, 'z'),
+                    ),
+                ],
+            ),
+        ];
+        let opts = Opts {
+            inconvs: indexset![
+                InputConverterType::DEC,
+                InputConverterType::HEX,
+                InputConverterType::BIN,
+            ],
+            outconvs: indexset![OutputConverterType::DEC],
+            ..Default::default()
+        };
+        for (input, exp) in tests {
+            match do_convert(input, &opts) {
+                Err(ConversionError::NoResults(rejections)) => assert_eq!(rejections.inner, exp),
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+    }
+
+    #[test]
+    fn do_convert_hint() {
+        let opts = Opts {
+            inconvs: indexset![InputConverterType::DEC, InputConverterType::HEX],
+            outconvs: indexset![OutputConverterType::HEX],
+            ..Default::default()
+        };
+        let tests = [
+            ("EAGAIN", Some(InputConverterType::CODE)),
+            ("NOTACODE", None),
+        ];
+        for (input, exp) in tests {
+            match do_convert(input, &opts) {
+                Err(ConversionError::NoResults(rejections)) => assert_eq!(rejections.hint, exp),
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+    }
+
+    #[test]
+    fn do_convert_table_columns() {
+        let opts = Opts {
+            inconvs: indexset![
+                InputConverterType::DEC,
+                InputConverterType::HEX,
+                InputConverterType::BIN,
+            ],
+            outconvs: indexset![OutputConverterType::DEC, OutputConverterType::HEX],
+            ..Default::default()
+        };
+        let inputs = ["10".to_string(), "0xFF".to_string()];
+        let table = do_convert_table(&inputs, &opts);
+        assert_eq!(
+            table.columns,
+            vec![
+                (InputConverterType::DEC, OutputConverterType::HEX),
+                (InputConverterType::HEX, OutputConverterType::DEC),
+            ]
+        );
+        assert_eq!(table.rows.len(), 2);
+    }
+
+    #[test]
+    fn do_convert_omitted() {
+        let opts = Opts {
+            inconvs: indexset![InputConverterType::DEC, InputConverterType::HEX],
+            outconvs: indexset![OutputConverterType::HEX],
+            ..Default::default()
+        };
+        match do_convert("FF", &opts) {
+            Err(ConversionError::NoResults(rejections)) => assert_eq!(
+                rejections.omitted,
+                vec![(InputConverterType::HEX, OutputConverterType::HEX)]
+            ),
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn do_convert_all_skipped() {
+        let opts = Opts {
+            inconvs: indexset![InputConverterType::DEC],
+            outconvs: indexset![OutputConverterType::HEX, OutputConverterType::ASCII],
+            ..Default::default()
+        };
+        match do_convert("-42", &opts) {
+            Err(ConversionError::NoResults(rejections)) => assert_eq!(
+                rejections.skipped,
+                vec![
+                    (
+                        InputConverterType::DEC,
+                        OutputConverterType::HEX,
+                        OutputError::Negative
+                    ),
+                    (
+                        InputConverterType::DEC,
+                        OutputConverterType::ASCII,
+                        OutputError::NotPrintable
+                    ),
+                ]
+            ),
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        let template = "{ascii}".parse().unwrap();
+        let opts = Opts {
+            inconvs: indexset![InputConverterType::DEC, InputConverterType::HEX],
+            ..Default::default()
+        };
+        let output = do_convert_template("100", &opts, &template).unwrap();
+        assert_eq!(output.lines, vec!["'d'"]);
+        assert_eq!(
+            output.skipped,
+            vec![(
+                InputConverterType::HEX,
+                OutputConverterType::ASCII,
+                OutputError::NotPrintable
+            )]
+        );
+        assert!(matches!(
+            do_convert_template("-1", &opts, &template),
+            Err(ConversionError::NoResults(_))
+        ));
+    }
 }
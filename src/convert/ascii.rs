@@ -1,17 +1,21 @@
 use nom::{character::complete::anychar, combinator::map_res, IResult};
 
-use super::{InputConverter, IntermediateValue, OutputConverter};
+use super::{InputConverter, InputError, IntermediateValue, OutputConverter, OutputError};
 
 /// Input converter that accepts ascii characters as inputs
 pub struct AsciiInputConverter;
 
 impl InputConverter for AsciiInputConverter {
-    fn convert(&self, input: &str) -> Result<IntermediateValue, ()> {
-        let (input, val) = parse_ascii_char(input).map_err(|_| ())?;
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        // Only the first character can be rejected by the parser
+        let (rest, val) = parse_ascii_char(input).map_err(|_| match input.chars().next() {
+            Some(c) => InputError::InvalidCharacter(0, c),
+            None => InputError::Empty,
+        })?;
 
         // Check if there are unconsumed characters
-        if input.len() > 0 {
-            return Err(());
+        if !rest.is_empty() {
+            return Err(InputError::trailing(input, rest));
         }
 
         Ok(val)
@@ -22,15 +26,25 @@ impl InputConverter for AsciiInputConverter {
 pub struct AsciiOutputConverter;
 
 impl OutputConverter for AsciiOutputConverter {
-    fn convert(&self, input: IntermediateValue) -> Result<String, ()> {
-        let input: u8 = input.try_into().map_err(|_| ())?;
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        let input: u8 = input.try_into().map_err(|_| OutputError::NotPrintable)?;
 
         // Don't encode non printable characters
-        if input < 32 || input > 126 {
-            return Err(());
+        if !(32..=126).contains(&input) {
+            return Err(OutputError::NotPrintable);
         }
 
-        return Ok(format!("'{}'", input as char));
+        Ok(format!("'{}'", input as char))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        Some(match self.convert(input) {
+            Ok(_) => vec![format!("ASCII code {} is {:?}", input, input as u8 as char)],
+            Err(_) => vec![format!(
+                "{} is not a printable ASCII character (32 to 126)",
+                input
+            )],
+        })
     }
 }
 
@@ -39,7 +53,7 @@ fn parse_ascii_char(input: &str) -> IResult<&str, IntermediateValue> {
     map_res(anychar, |c| {
         let val = c as i128;
         // Error on non printable characters
-        if val < 32 || val > 126 {
+        if !(32..=126).contains(&val) {
             Err(())
         } else {
             Ok(val)
@@ -62,10 +76,15 @@ mod tests {
 
     #[test]
     fn ascii_inconv_err() {
-        let tests = ["", "abcdefg", "\t", "'a'"];
+        let tests = [
+            ("", InputError::Empty),
+            ("abcdefg", InputError::InvalidCharacter(1, 'b')),
+            ("\t", InputError::InvalidCharacter(0, '\t')),
+            ("'a'", InputError::InvalidCharacter(1, 'a')),
+        ];
         let conv = AsciiInputConverter;
-        for input in tests {
-            conv.convert(input).unwrap_err();
+        for (input, exp) in tests {
+            assert_eq!(conv.convert(input).unwrap_err(), exp);
         }
     }
 
@@ -86,4 +105,14 @@ mod tests {
             conv.convert(input).unwrap_err();
         }
     }
+
+    #[test]
+    fn ascii_outconv_explain() {
+        let conv = AsciiOutputConverter;
+        assert_eq!(conv.explain(65).unwrap(), vec!["ASCII code 65 is 'A'"]);
+        assert_eq!(
+            conv.explain(200).unwrap(),
+            vec!["200 is not a printable ASCII character (32 to 126)"]
+        );
+    }
 }
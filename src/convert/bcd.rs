@@ -0,0 +1,245 @@
+use super::{InputConverter, InputError, IntermediateValue, OutputConverter, OutputError};
+
+/// Input converter that accepts packed BCD, one decimal digit per nibble, written in hexadecimal
+pub struct BcdInputConverter;
+
+impl InputConverter for BcdInputConverter {
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        decode_nibbles(input, |_, c| c.is_ascii_digit().then_some(Some(c)))
+    }
+}
+
+/// Output converter that gives packed BCD, one decimal digit per nibble, in hexadecimal
+pub struct BcdOutputConverter;
+
+impl OutputConverter for BcdOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        // The nibbles of packed BCD read as the decimal digits
+        encode_digits(input, |digit| digit.to_string())
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        Some(explain_digits(input, |digit| {
+            format!("{} = {:04b}", digit, digit)
+        }))
+    }
+}
+
+/// Input converter that accepts unpacked BCD, one decimal digit per byte, written in hexadecimal
+pub struct UnpackedBcdInputConverter;
+
+impl InputConverter for UnpackedBcdInputConverter {
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        // High nibbles are 0, counting bytes from the end
+        decode_nibbles(input, |position, c| {
+            if position % 2 == 1 {
+                (c == '0').then_some(None)
+            } else {
+                c.is_ascii_digit().then_some(Some(c))
+            }
+        })
+    }
+}
+
+/// Output converter that gives unpacked BCD, one decimal digit per byte, in hexadecimal
+pub struct UnpackedBcdOutputConverter;
+
+impl OutputConverter for UnpackedBcdOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        encode_digits(input, |digit| format!("0{}", digit))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        Some(explain_digits(input, |digit| {
+            format!("{} = 0000{:04b}", digit, digit)
+        }))
+    }
+}
+
+/// Input converter that accepts excess-3, each decimal digit plus 3 per nibble, in hexadecimal
+pub struct Excess3InputConverter;
+
+impl InputConverter for Excess3InputConverter {
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        decode_nibbles(input, |_, c| {
+            let digit = c.to_digit(16).filter(|nibble| (3..=12).contains(nibble))? - 3;
+            Some(char::from_digit(digit, 10))
+        })
+    }
+}
+
+/// Output converter that gives excess-3, each decimal digit plus 3 per nibble, in hexadecimal
+pub struct Excess3OutputConverter;
+
+impl OutputConverter for Excess3OutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        encode_digits(input, |digit| format!("{:X}", digit + 3))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        Some(explain_digits(input, |digit| {
+            format!("{} + 3 = {:04b}", digit, digit + 3)
+        }))
+    }
+}
+
+/// Decode the decimal digits held by the nibbles of a hexadecimal value, with optional 0x prefix
+///
+/// Each nibble is given with its position from the end, and decoded as a decimal digit, as
+/// padding, or rejected
+fn decode_nibbles(
+    input: &str,
+    decode: impl Fn(usize, char) -> Option<Option<char>>,
+) -> Result<IntermediateValue, InputError> {
+    let prefix = match input.get(..2) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("0x") => prefix,
+        _ => "",
+    };
+    let nibbles = &input[prefix.len()..];
+    if nibbles.is_empty() {
+        return Err(match prefix {
+            "" => InputError::Empty,
+            prefix => InputError::EmptyAfterPrefix(prefix.to_string()),
+        });
+    }
+
+    let count = nibbles.chars().count();
+    let mut digits = String::new();
+    for (i, c) in nibbles.chars().enumerate() {
+        match decode(count - 1 - i, c) {
+            Some(Some(digit)) => digits.push(digit),
+            Some(None) => {}
+            None => return Err(InputError::InvalidCharacter(prefix.len() + i, c)),
+        }
+    }
+
+    digits.parse().map_err(|_| InputError::Overflow)
+}
+
+/// Encode each decimal digit of a value
+fn encode_digits(
+    input: IntermediateValue,
+    encode: impl Fn(u32) -> String,
+) -> Result<String, OutputError> {
+    // Don't encode negative values
+    if input < 0 {
+        return Err(OutputError::Negative);
+    }
+    Ok(input
+        .to_string()
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(encode)
+        .collect())
+}
+
+/// Explain how each decimal digit of a value is encoded
+fn explain_digits(input: IntermediateValue, explain: impl Fn(u32) -> String) -> Vec<String> {
+    match encode_digits(input, &explain) {
+        Ok(_) => input
+            .to_string()
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(explain)
+            .collect(),
+        Err(err) => vec![err.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bcd_inconv_ok() {
+        let tests: [(&dyn InputConverter, &str, IntermediateValue); 7] = [
+            (&BcdInputConverter, "1234", 1234),
+            (&BcdInputConverter, "0x59", 59),
+            (&UnpackedBcdInputConverter, "0x0509", 59),
+            (&UnpackedBcdInputConverter, "509", 59),
+            (&UnpackedBcdInputConverter, "00", 0),
+            (&Excess3InputConverter, "0x8C", 59),
+            (&Excess3InputConverter, "4a", 17),
+        ];
+        for (conv, input, exp) in tests {
+            assert_eq!(conv.convert(input).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn bcd_inconv_err() {
+        let tests: [(&dyn InputConverter, &str, InputError); 8] = [
+            (&BcdInputConverter, "", InputError::Empty),
+            (
+                &BcdInputConverter,
+                "0x",
+                InputError::EmptyAfterPrefix("0x".to_string()),
+            ),
+            (
+                &BcdInputConverter,
+                "0x12A4",
+                InputError::InvalidCharacter(4, 'A'),
+            ),
+            (
+                &UnpackedBcdInputConverter,
+                "0x0519",
+                InputError::InvalidCharacter(4, '1'),
+            ),
+            (
+                &UnpackedBcdInputConverter,
+                "0A",
+                InputError::InvalidCharacter(1, 'A'),
+            ),
+            (
+                &Excess3InputConverter,
+                "82",
+                InputError::InvalidCharacter(1, '2'),
+            ),
+            (
+                &Excess3InputConverter,
+                "3D",
+                InputError::InvalidCharacter(1, 'D'),
+            ),
+            (&BcdInputConverter, &"9".repeat(40), InputError::Overflow),
+        ];
+        for (conv, input, exp) in tests {
+            assert_eq!(conv.convert(input), Err(exp));
+        }
+    }
+
+    #[test]
+    fn bcd_outconv_ok() {
+        let tests: [(&dyn OutputConverter, IntermediateValue, &str); 5] = [
+            (&BcdOutputConverter, 1234, "1234"),
+            (&UnpackedBcdOutputConverter, 59, "0509"),
+            (&UnpackedBcdOutputConverter, 0, "00"),
+            (&Excess3OutputConverter, 59, "8C"),
+            (&Excess3OutputConverter, 0, "3"),
+        ];
+        for (conv, input, exp) in tests {
+            assert_eq!(conv.convert(input).unwrap(), exp);
+        }
+        assert_eq!(BcdOutputConverter.convert(-1), Err(OutputError::Negative));
+    }
+
+    #[test]
+    fn bcd_outconv_explain() {
+        let tests: [(&dyn OutputConverter, IntermediateValue, Vec<&str>); 4] = [
+            (&BcdOutputConverter, 59, vec!["5 = 0101", "9 = 1001"]),
+            (&UnpackedBcdOutputConverter, 7, vec!["7 = 00000111"]),
+            (
+                &Excess3OutputConverter,
+                59,
+                vec!["5 + 3 = 1000", "9 + 3 = 1100"],
+            ),
+            (
+                &BcdOutputConverter,
+                -1,
+                vec!["negative value has no unsigned representation"],
+            ),
+        ];
+        for (conv, input, exp) in tests {
+            assert_eq!(conv.explain(input).unwrap(), exp);
+        }
+    }
+}
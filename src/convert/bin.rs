@@ -6,18 +6,18 @@ use nom::{
     IResult, InputTakeAtPosition,
 };
 
-use super::{InputConverter, IntermediateValue, OutputConverter};
+use super::{InputConverter, InputError, IntermediateValue, OutputConverter, OutputError};
 
 /// Input converter that accepts hexadecimal numbers as inputs
 pub struct BinInputConverter;
 
 impl InputConverter for BinInputConverter {
-    fn convert(&self, input: &str) -> Result<IntermediateValue, ()> {
-        let (input, val) = parse_pos_bin_int(input).map_err(|_| ())?;
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        let (rest, val) = parse_pos_bin_int(input).map_err(|e| InputError::from_nom(input, e))?;
 
         // Check if there are unconsumed characters
-        if input.len() > 0 {
-            return Err(());
+        if !rest.is_empty() {
+            return Err(InputError::trailing(input, rest));
         }
 
         Ok(val)
@@ -28,12 +28,12 @@ impl InputConverter for BinInputConverter {
 pub struct BinOutputConverter;
 
 impl OutputConverter for BinOutputConverter {
-    fn convert(&self, input: IntermediateValue) -> Result<String, ()> {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
         // Don't encode negative values
         if input < 0 {
-            return Err(());
+            return Err(OutputError::Negative);
         }
-        return Ok(format!("{:b}", input));
+        Ok(format!("{:b}", input))
     }
 }
 
@@ -72,10 +72,18 @@ mod tests {
 
     #[test]
     fn bin_inconv_err() {
-        let tests = ["", "abcdefg", "1234", "0b", "0x10010"];
+        let tests = [
+            ("", InputError::Empty),
+            ("abcdefg", InputError::InvalidCharacter(0, 'a')),
+            ("1234", InputError::InvalidCharacter(1, '2')),
+            ("0b", InputError::EmptyAfterPrefix("0b".to_string())),
+            ("0x10010", InputError::InvalidCharacter(1, 'x')),
+            ("0b102", InputError::InvalidCharacter(4, '2')),
+            (&"1".repeat(128), InputError::Overflow),
+        ];
         let conv = BinInputConverter;
-        for input in tests {
-            conv.convert(input).unwrap_err();
+        for (input, exp) in tests {
+            assert_eq!(conv.convert(input).unwrap_err(), exp);
         }
     }
 
@@ -0,0 +1,192 @@
+use super::{IntermediateValue, OutputConverter, OutputError};
+
+/// Widths tried, in order, when no width is selected
+const COMMON_WIDTHS: [u32; 5] = [8, 16, 32, 64, 128];
+
+/// Output converter that gives a breakdown of the bits of a value
+///
+/// Negative values are taken in two's complement
+pub struct BitsOutputConverter {
+    /// Width in bits, the smallest common width that fits the value if None
+    pub width: Option<u32>,
+}
+
+impl OutputConverter for BitsOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        let width = self.width.unwrap_or_else(|| fitting_width(input));
+        if !fits_width(input, width) {
+            return Err(OutputError::DoesNotFit(width));
+        }
+        let bits = input as u128 & mask(width);
+
+        let set: Vec<String> = (0..width)
+            .rev()
+            .filter(|bit| bits >> bit & 1 == 1)
+            .map(|bit| bit.to_string())
+            .collect();
+        let highest = (bits != 0).then(|| 127 - bits.leading_zeros());
+        let lz = highest.map_or(width, |highest| width - highest - 1);
+        let tz = if bits == 0 {
+            width
+        } else {
+            bits.trailing_zeros()
+        };
+
+        // Closest powers of two, strictly above and below, within the width
+        let next = match highest {
+            Some(highest) if highest + 1 >= width => None,
+            Some(highest) => Some(1u128 << (highest + 1)),
+            None => Some(1),
+        };
+        let prev = match highest {
+            Some(highest) if bits.is_power_of_two() => highest.checked_sub(1).map(|bit| 1 << bit),
+            Some(highest) => Some(1u128 << highest),
+            None => None,
+        };
+
+        Ok(format!(
+            "set {}; popcount {}; lz {}; tz {}; highest {}; pow2 {}; next {}; prev {}; {} bits",
+            or_none((!set.is_empty()).then(|| set.join(" "))),
+            bits.count_ones(),
+            lz,
+            tz,
+            or_none(highest),
+            if bits.is_power_of_two() { "yes" } else { "no" },
+            or_none(next),
+            or_none(prev),
+            width
+        ))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        let width = self.width.unwrap_or_else(|| fitting_width(input));
+        Some(match self.convert(input) {
+            Ok(breakdown) => {
+                let w = width as usize;
+                let bits = input as u128 & mask(width);
+                vec![
+                    format!("{} on {} bits: {:0w$b}", input, width, bits),
+                    breakdown,
+                ]
+            }
+            Err(err) => vec![err.to_string()],
+        })
+    }
+}
+
+/// Smallest common width that fits a value, in two's complement if negative
+pub fn fitting_width(value: IntermediateValue) -> u32 {
+    COMMON_WIDTHS
+        .into_iter()
+        .find(|width| fits_width(value, *width))
+        .unwrap_or(IntermediateValue::BITS)
+}
+
+/// Check if a value can be written in a width, in two's complement if negative
+pub fn fits_width(value: IntermediateValue, width: u32) -> bool {
+    if width >= IntermediateValue::BITS {
+        true
+    } else if value < 0 {
+        value >= -(1 << (width - 1))
+    } else {
+        value as u128 >> width == 0
+    }
+}
+
+/// Mask of the lowest bits of a width
+pub fn mask(width: u32) -> u128 {
+    u128::MAX >> (u128::BITS - width)
+}
+
+/// Display value, or "none" if there isn't one
+fn or_none(value: Option<impl ToString>) -> String {
+    value.map_or("none".to_string(), |value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits_outconv_ok() {
+        let tests = [
+            (
+                42,
+                None,
+                "set 5 3 1; popcount 3; lz 2; tz 1; highest 5; pow2 no; next 64; prev 32; 8 bits",
+            ),
+            (
+                0,
+                None,
+                "set none; popcount 0; lz 8; tz 8; highest none; pow2 no; next 1; prev none; 8 bits",
+            ),
+            (
+                1,
+                Some(4),
+                "set 0; popcount 1; lz 3; tz 0; highest 0; pow2 yes; next 2; prev none; 4 bits",
+            ),
+            (
+                256,
+                None,
+                "set 8; popcount 1; lz 7; tz 8; highest 8; pow2 yes; next 512; prev 128; 16 bits",
+            ),
+            (
+                -1,
+                Some(4),
+                "set 3 2 1 0; popcount 4; lz 0; tz 0; highest 3; pow2 no; next none; prev 8; 4 bits",
+            ),
+            (
+                -128,
+                None,
+                "set 7; popcount 1; lz 0; tz 7; highest 7; pow2 yes; next none; prev 64; 8 bits",
+            ),
+        ];
+        for (input, width, exp) in tests {
+            let converter = BitsOutputConverter { width };
+            assert_eq!(converter.convert(input).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn bits_outconv_err() {
+        let tests = [(256, 8), (-129, 8), (16, 4)];
+        for (input, width) in tests {
+            let converter = BitsOutputConverter { width: Some(width) };
+            assert_eq!(
+                converter.convert(input),
+                Err(OutputError::DoesNotFit(width))
+            );
+        }
+    }
+
+    #[test]
+    fn bits_outconv_explain() {
+        assert_eq!(
+            BitsOutputConverter { width: None }.explain(-6).unwrap(),
+            vec![
+                "-6 on 8 bits: 11111010",
+                "set 7 6 5 4 3 1; popcount 6; lz 0; tz 1; highest 7; pow2 no; next none; prev 128; 8 bits",
+            ]
+        );
+        assert_eq!(
+            BitsOutputConverter { width: Some(8) }.explain(300).unwrap(),
+            vec!["value does not fit in 8 bits"]
+        );
+    }
+
+    #[test]
+    fn fitting_width_ok() {
+        let tests = [
+            (0, 8),
+            (255, 8),
+            (256, 16),
+            (-128, 8),
+            (-129, 16),
+            (IntermediateValue::MAX, 128),
+            (IntermediateValue::MIN, 128),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(fitting_width(input), exp);
+        }
+    }
+}
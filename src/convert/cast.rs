@@ -0,0 +1,181 @@
+use super::{bits::mask, IntermediateValue, OutputConverter, OutputError};
+
+/// Primitive integer types, as their Rust name, width in bits and signedness
+const INTEGER_TYPES: [(&str, u32, bool); 10] = [
+    ("u8", 8, false),
+    ("i8", 8, true),
+    ("u16", 16, false),
+    ("i16", 16, true),
+    ("u32", 32, false),
+    ("i32", 32, true),
+    ("u64", 64, false),
+    ("i64", 64, true),
+    ("u128", 128, false),
+    ("i128", 128, true),
+];
+
+/// Value cast to a primitive integer type
+#[derive(Debug, PartialEq)]
+pub struct Cast {
+    pub name: &'static str,
+    pub width: u32,
+    /// Value given by a wrapping cast, as Rust `as` and C casts do
+    pub wrapped: String,
+    /// Whether the type holds the value, so that no cast changes it
+    pub lossless: bool,
+    /// Value given by a saturating cast
+    pub saturated: String,
+}
+
+impl Cast {
+    /// Cast a value to every primitive integer type, from the smallest
+    pub fn all(value: IntermediateValue) -> Vec<Self> {
+        INTEGER_TYPES
+            .iter()
+            .map(|&(name, width, signed)| Self::new(name, width, signed, value))
+            .collect()
+    }
+
+    /// Cast a value to a primitive integer type
+    fn new(name: &'static str, width: u32, signed: bool, value: IntermediateValue) -> Self {
+        let bits = value as u128 & mask(width);
+        let unused = u128::BITS - width;
+        let (wrapped, min, max) = if signed {
+            let max = mask(width - 1) as IntermediateValue;
+            let wrapped = (bits << unused) as IntermediateValue >> unused;
+            (wrapped.to_string(), -max - 1, max.to_string())
+        } else {
+            (bits.to_string(), 0, mask(width).to_string())
+        };
+
+        let lossless = wrapped == value.to_string();
+        let saturated = if lossless {
+            wrapped.clone()
+        } else if value < min {
+            min.to_string()
+        } else {
+            max
+        };
+
+        Self {
+            name,
+            width,
+            wrapped,
+            lossless,
+            saturated,
+        }
+    }
+}
+
+/// Output converter that shows a value cast to every primitive integer type
+pub struct CastOutputConverter;
+
+impl OutputConverter for CastOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        let casts: Vec<String> = Cast::all(input)
+            .into_iter()
+            .map(|cast| {
+                if cast.lossless {
+                    format!("{} {}", cast.name, cast.wrapped)
+                } else {
+                    format!(
+                        "{} {} (lossy, saturating: {})",
+                        cast.name, cast.wrapped, cast.saturated
+                    )
+                }
+            })
+            .collect();
+        Ok(casts.join(", "))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        let steps = Cast::all(input)
+            .into_iter()
+            .map(|cast| {
+                if cast.lossless {
+                    format!("{}: {} fits, casts keep it", cast.name, input)
+                } else {
+                    format!(
+                        "{}: {} does not fit, wraps modulo 2^{} to {}, saturates to {}",
+                        cast.name, input, cast.width, cast.wrapped, cast.saturated
+                    )
+                }
+            })
+            .collect();
+        Some(steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cast_all_ok() {
+        let tests = [
+            (300, "u8", "44", false, "255"),
+            (300, "i8", "44", false, "127"),
+            (300, "i16", "300", true, "300"),
+            (200, "i8", "-56", false, "127"),
+            (-1, "u8", "255", false, "0"),
+            (-1, "i8", "-1", true, "-1"),
+            (-129, "i8", "127", false, "-128"),
+            (
+                -1,
+                "u128",
+                "340282366920938463463374607431768211455",
+                false,
+                "0",
+            ),
+            (
+                IntermediateValue::MIN,
+                "i64",
+                "0",
+                false,
+                "-9223372036854775808",
+            ),
+            (
+                IntermediateValue::MAX,
+                "i128",
+                "170141183460469231731687303715884105727",
+                true,
+                "170141183460469231731687303715884105727",
+            ),
+        ];
+        for (value, name, wrapped, lossless, saturated) in tests {
+            let cast = Cast::all(value)
+                .into_iter()
+                .find(|cast| cast.name == name)
+                .unwrap();
+            assert_eq!(
+                (
+                    cast.wrapped.as_str(),
+                    cast.lossless,
+                    cast.saturated.as_str()
+                ),
+                (wrapped, lossless, saturated)
+            );
+        }
+    }
+
+    #[test]
+    fn cast_outconv_ok() {
+        assert_eq!(
+            CastOutputConverter.convert(-1).unwrap(),
+            "u8 255 (lossy, saturating: 0), i8 -1, u16 65535 (lossy, saturating: 0), i16 -1, \
+             u32 4294967295 (lossy, saturating: 0), i32 -1, \
+             u64 18446744073709551615 (lossy, saturating: 0), i64 -1, \
+             u128 340282366920938463463374607431768211455 (lossy, saturating: 0), i128 -1"
+        );
+    }
+
+    #[test]
+    fn cast_outconv_explain() {
+        let steps = CastOutputConverter.explain(300).unwrap();
+        assert_eq!(
+            steps[0],
+            "u8: 300 does not fit, wraps modulo 2^8 to 44, saturates to 255"
+        );
+        assert_eq!(steps[3], "i16: 300 fits, casts keep it");
+    }
+}
@@ -0,0 +1,564 @@
+mod tables;
+
+use super::{InputConverter, InputError, IntermediateValue, OutputConverter, OutputError};
+
+use tables::{
+    ERRNO, EXIT_STATUSES, HRESULTS, HRESULT_FACILITIES, HTTP_CLASSES, HTTP_STATUSES, NTSTATUSES,
+    NTSTATUS_FACILITIES, NTSTATUS_SEVERITIES, REALTIME_SIGNALS, SIGNALS, WIN32_ERRORS,
+};
+
+/// Exit statuses above this one report the signal that killed the process
+const SIGNAL_EXIT_BASE: u32 = 128;
+
+/// HRESULT bit marking a value made from an NTSTATUS
+const HRESULT_NT_BIT: u32 = 1 << 28;
+
+/// HRESULT and NTSTATUS bit marking a value defined by a third party
+const CUSTOMER_BIT: u32 = 1 << 29;
+
+/// Output converter that names Linux errno values, also when negated as returned by syscalls
+pub struct ErrnoOutputConverter;
+
+impl OutputConverter for ErrnoOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        let (name, description) = u32::try_from(input.unsigned_abs())
+            .ok()
+            .and_then(|value| find(ERRNO, value))
+            .ok_or(OutputError::UnknownCode)?;
+        Ok(format!("{}: {}", name, description))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        let steps = if input < 0 {
+            vec![format!(
+                "syscalls return errors negated: errno is -({}) = {}",
+                input,
+                input.unsigned_abs()
+            )]
+        } else {
+            vec![]
+        };
+        Some(with_meaning(self, input, steps))
+    }
+}
+
+/// Output converter that names Linux signals
+pub struct SignalOutputConverter;
+
+impl OutputConverter for SignalOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        if input < 0 {
+            return Err(OutputError::Negative);
+        }
+        let (name, description) = u32::try_from(input)
+            .ok()
+            .and_then(signal)
+            .ok_or(OutputError::UnknownCode)?;
+        Ok(format!("{}: {}", name, description))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        Some(with_meaning(self, input, vec![]))
+    }
+}
+
+/// Output converter that explains process exit statuses. Ex: 139 is SIGSEGV (128+11)
+pub struct ExitStatusOutputConverter;
+
+impl OutputConverter for ExitStatusOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        if input < 0 {
+            return Err(OutputError::Negative);
+        }
+        let status = u8::try_from(input).map_err(|_| OutputError::DoesNotFit(8))? as u32;
+
+        if let Some((name, _)) = status
+            .checked_sub(SIGNAL_EXIT_BASE)
+            .filter(|signal| *signal > 0)
+            .and_then(signal)
+        {
+            return Ok(format!(
+                "{} ({}+{})",
+                name,
+                SIGNAL_EXIT_BASE,
+                status - SIGNAL_EXIT_BASE
+            ));
+        }
+        Ok(match find(EXIT_STATUSES, status) {
+            Some(("", description)) => description.to_string(),
+            Some((name, description)) => format!("{}: {}", name, description),
+            None => "failure".to_string(),
+        })
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        let base = SIGNAL_EXIT_BASE as IntermediateValue;
+        let steps = if (base + 1..=255).contains(&input) {
+            vec![format!(
+                "statuses above {} are set by a signal: {} = {} + {}",
+                base,
+                input,
+                base,
+                input - base
+            )]
+        } else {
+            vec![]
+        };
+        Some(with_meaning(self, input, steps))
+    }
+}
+
+/// Output converter that gives the reason phrase and class of HTTP status codes
+pub struct HttpStatusOutputConverter;
+
+impl OutputConverter for HttpStatusOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        let status = u32::try_from(input)
+            .ok()
+            .filter(|status| (100..600).contains(status))
+            .ok_or(OutputError::UnknownCode)?;
+        let class = HTTP_CLASSES[(status / 100 - 1) as usize];
+        let reason = name(HTTP_STATUSES, status).unwrap_or("unassigned");
+        Ok(format!("{} ({})", reason, class))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        let steps = if (100..600).contains(&input) {
+            vec![format!("the first digit {} gives the class", input / 100)]
+        } else {
+            vec![]
+        };
+        Some(with_meaning(self, input, steps))
+    }
+}
+
+/// Output converter that decodes Windows HRESULT values
+///
+/// Ex: E_ACCESSDENIED; failure; facility WIN32 (7); code ERROR_ACCESS_DENIED (5)
+pub struct HresultOutputConverter;
+
+impl OutputConverter for HresultOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        let value = as_u32(input)?;
+        let mut parts: Vec<String> = name(HRESULTS, value)
+            .map(str::to_string)
+            .into_iter()
+            .collect();
+        parts.push(
+            if value >> 31 == 1 {
+                "failure"
+            } else {
+                "success"
+            }
+            .to_string(),
+        );
+        if value & CUSTOMER_BIT != 0 {
+            parts.push("customer".to_string());
+        }
+
+        // Values made from an NTSTATUS keep it in the other bits
+        if value & HRESULT_NT_BIT != 0 {
+            let status = value & !HRESULT_NT_BIT;
+            parts.push(match name(NTSTATUSES, status) {
+                Some(name) => format!("from NTSTATUS 0x{:08X} ({})", status, name),
+                None => format!("from NTSTATUS 0x{:08X}", status),
+            });
+            return Ok(parts.join("; "));
+        }
+
+        let facility = value >> 16 & 0x7FF;
+        let code = value & 0xFFFF;
+        parts.push(format!(
+            "facility {}",
+            with_name(HRESULT_FACILITIES, facility)
+        ));
+        parts.push(match facility {
+            7 => format!("code {}", with_name(WIN32_ERRORS, code)),
+            _ => format!("code {}", code),
+        });
+        Ok(parts.join("; "))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        let bits = input as u32;
+        let steps = vec![
+            format!("severity (bit 31) = {}", bits >> 31),
+            format!("customer (bit 29) = {}", bits >> 29 & 1),
+            format!("from NTSTATUS (bit 28) = {}", bits >> 28 & 1),
+            format!("facility (bits 26:16) = {}", bits >> 16 & 0x7FF),
+            format!("code (bits 15:0) = {}", bits & 0xFFFF),
+        ];
+        Some(with_meaning(self, input, steps))
+    }
+}
+
+/// Output converter that decodes Windows NTSTATUS values
+///
+/// Ex: STATUS_ACCESS_VIOLATION; error; facility 0; code 5
+pub struct NtstatusOutputConverter;
+
+impl OutputConverter for NtstatusOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        let value = as_u32(input)?;
+        let mut parts: Vec<String> = name(NTSTATUSES, value)
+            .map(str::to_string)
+            .into_iter()
+            .collect();
+        parts.push(NTSTATUS_SEVERITIES[(value >> 30) as usize].to_string());
+        if value & CUSTOMER_BIT != 0 {
+            parts.push("customer".to_string());
+        }
+        parts.push(format!(
+            "facility {}",
+            with_name(NTSTATUS_FACILITIES, value >> 16 & 0xFFF)
+        ));
+        parts.push(format!("code {}", value & 0xFFFF));
+        Ok(parts.join("; "))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        let bits = input as u32;
+        let steps = vec![
+            format!("severity (bits 31:30) = {}", bits >> 30),
+            format!("customer (bit 29) = {}", bits >> 29 & 1),
+            format!("facility (bits 27:16) = {}", bits >> 16 & 0xFFF),
+            format!("code (bits 15:0) = {}", bits & 0xFFFF),
+        ];
+        Some(with_meaning(self, input, steps))
+    }
+}
+
+/// Input converter that looks up the value of a well-known name
+///
+/// Ex: EAGAIN, SIGSEGV, SEGV, SIGRTMIN+2, EX_USAGE, E_FAIL, STATUS_ACCESS_VIOLATION, "Not Found"
+pub struct CodeInputConverter;
+
+impl InputConverter for CodeInputConverter {
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(InputError::Empty);
+        }
+
+        let upper = input.to_ascii_uppercase();
+        let signal = upper.strip_prefix("SIG").unwrap_or(&upper);
+        let with_description = ERRNO.iter().chain(SIGNALS).chain(EXIT_STATUSES);
+        let named = HRESULTS.iter().chain(WIN32_ERRORS).chain(NTSTATUSES);
+
+        with_description
+            .map(|(value, name, _)| (value, name))
+            .chain(named.map(|(value, name)| (value, name)))
+            .find(|(_, name)| **name == upper || name.strip_prefix("SIG") == Some(signal))
+            .map(|(value, _)| *value)
+            .or_else(|| realtime_signal(signal))
+            .or_else(|| {
+                // Reason phrases are matched on their letters and digits only
+                HTTP_STATUSES
+                    .iter()
+                    .find(|(_, reason)| normalize(reason) == normalize(input))
+                    .map(|(value, _)| *value)
+            })
+            .map(IntermediateValue::from)
+            .ok_or_else(|| InputError::UnknownName(input.to_string()))
+    }
+}
+
+/// Find the name and description of a value in a table
+fn find(
+    table: &[(u32, &'static str, &'static str)],
+    value: u32,
+) -> Option<(&'static str, &'static str)> {
+    table
+        .iter()
+        .find(|(entry, _, _)| *entry == value)
+        .map(|(_, name, description)| (*name, *description))
+}
+
+/// Find the name of a value in a table
+fn name(table: &[(u32, &'static str)], value: u32) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(entry, _)| *entry == value)
+        .map(|(_, name)| *name)
+}
+
+/// Value followed by its name if known. Ex: WIN32 (7)
+fn with_name(table: &[(u32, &'static str)], value: u32) -> String {
+    match name(table, value) {
+        Some(name) => format!("{} ({})", name, value),
+        None => value.to_string(),
+    }
+}
+
+/// Name and description of a standard or real-time signal
+fn signal(value: u32) -> Option<(String, String)> {
+    let (min, max) = REALTIME_SIGNALS;
+    match find(SIGNALS, value) {
+        Some((name, description)) => Some((name.to_string(), description.to_string())),
+        None if value == min => Some(("SIGRTMIN".to_string(), "Real-time signal 0".to_string())),
+        None if value == max => Some((
+            "SIGRTMAX".to_string(),
+            format!("Real-time signal {}", max - min),
+        )),
+        None if (min..max).contains(&value) => Some((
+            format!("SIGRTMIN+{}", value - min),
+            format!("Real-time signal {}", value - min),
+        )),
+        None => None,
+    }
+}
+
+/// Value of a real-time signal name, without the SIG prefix. Ex: RTMIN+2, RTMAX-1
+fn realtime_signal(name: &str) -> Option<u32> {
+    let (min, max) = REALTIME_SIGNALS;
+    let value = match name {
+        "RTMIN" => min,
+        "RTMAX" => max,
+        _ => match name.strip_prefix("RTMIN+") {
+            Some(offset) => min.checked_add(offset.parse().ok()?)?,
+            None => max.checked_sub(name.strip_prefix("RTMAX-")?.parse().ok()?)?,
+        },
+    };
+    (min..=max).contains(&value).then_some(value)
+}
+
+/// Value as 32 bits, negative values being taken in two's complement
+fn as_u32(value: IntermediateValue) -> Result<u32, OutputError> {
+    u32::try_from(value)
+        .or_else(|_| i32::try_from(value).map(|value| value as u32))
+        .map_err(|_| OutputError::DoesNotFit(32))
+}
+
+/// Follow the steps explaining a code with its meaning, or replace them with why it has none
+fn with_meaning(
+    conv: &impl OutputConverter,
+    input: IntermediateValue,
+    mut steps: Vec<String>,
+) -> Vec<String> {
+    match conv.convert(input) {
+        Ok(meaning) => steps.push(format!("which means {}", meaning)),
+        Err(err) => steps = vec![err.to_string()],
+    }
+    steps
+}
+
+/// Lower case letters and digits of a name
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errno_outconv_ok() {
+        let tests = [
+            (11, "EAGAIN: Resource temporarily unavailable"),
+            (-11, "EAGAIN: Resource temporarily unavailable"),
+            (2, "ENOENT: No such file or directory"),
+            (133, "EHWPOISON: Memory page has hardware error"),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(ErrnoOutputConverter.convert(input).unwrap(), exp);
+        }
+        for input in [0, 41, 134, IntermediateValue::MIN] {
+            assert_eq!(
+                ErrnoOutputConverter.convert(input),
+                Err(OutputError::UnknownCode)
+            );
+        }
+    }
+
+    #[test]
+    fn signal_outconv_ok() {
+        let tests = [
+            (11, "SIGSEGV: Segmentation fault"),
+            (6, "SIGABRT: Aborted"),
+            (34, "SIGRTMIN: Real-time signal 0"),
+            (37, "SIGRTMIN+3: Real-time signal 3"),
+            (64, "SIGRTMAX: Real-time signal 30"),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(SignalOutputConverter.convert(input).unwrap(), exp);
+        }
+        assert_eq!(
+            SignalOutputConverter.convert(32),
+            Err(OutputError::UnknownCode)
+        );
+        assert_eq!(
+            SignalOutputConverter.convert(-9),
+            Err(OutputError::Negative)
+        );
+    }
+
+    #[test]
+    fn exit_status_outconv_ok() {
+        let tests = [
+            (0, "EXIT_SUCCESS: success"),
+            (2, "misuse of shell builtins"),
+            (64, "EX_USAGE: command line usage error"),
+            (127, "command not found"),
+            (128, "failure"),
+            (137, "SIGKILL (128+9)"),
+            (139, "SIGSEGV (128+11)"),
+            (192, "SIGRTMAX (128+64)"),
+            (201, "failure"),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(ExitStatusOutputConverter.convert(input).unwrap(), exp);
+        }
+        assert_eq!(
+            ExitStatusOutputConverter.convert(256),
+            Err(OutputError::DoesNotFit(8))
+        );
+        assert_eq!(
+            ExitStatusOutputConverter.convert(-1),
+            Err(OutputError::Negative)
+        );
+    }
+
+    #[test]
+    fn http_status_outconv_ok() {
+        let tests = [
+            (200, "OK (success)"),
+            (404, "Not Found (client error)"),
+            (418, "I'm a teapot (client error)"),
+            (599, "unassigned (server error)"),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(HttpStatusOutputConverter.convert(input).unwrap(), exp);
+        }
+        for input in [99, 600, -404] {
+            assert_eq!(
+                HttpStatusOutputConverter.convert(input),
+                Err(OutputError::UnknownCode)
+            );
+        }
+    }
+
+    #[test]
+    fn hresult_outconv_ok() {
+        let tests = [
+            (0, "S_OK; success; facility NULL (0); code 0"),
+            (
+                0x80070005,
+                "E_ACCESSDENIED; failure; facility WIN32 (7); code ERROR_ACCESS_DENIED (5)",
+            ),
+            (
+                -2147024894,
+                "failure; facility WIN32 (7); code ERROR_FILE_NOT_FOUND (2)",
+            ),
+            (0x80040200, "failure; facility ITF (4); code 512"),
+            (0xA0FF0001, "failure; customer; facility 255; code 1"),
+            (
+                0xD0000005,
+                "failure; from NTSTATUS 0xC0000005 (STATUS_ACCESS_VIOLATION)",
+            ),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(HresultOutputConverter.convert(input).unwrap(), exp);
+        }
+        assert_eq!(
+            HresultOutputConverter.convert(0x100000000),
+            Err(OutputError::DoesNotFit(32))
+        );
+    }
+
+    #[test]
+    fn ntstatus_outconv_ok() {
+        let tests = [
+            (0, "STATUS_SUCCESS; success; facility 0; code 0"),
+            (
+                0xC0000005,
+                "STATUS_ACCESS_VIOLATION; error; facility 0; code 5",
+            ),
+            (
+                -1073741819,
+                "STATUS_ACCESS_VIOLATION; error; facility 0; code 5",
+            ),
+            (0x80000003, "STATUS_BREAKPOINT; warning; facility 0; code 3"),
+            (0x40010001, "informational; facility DEBUGGER (1); code 1"),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(NtstatusOutputConverter.convert(input).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn codes_outconv_explain() {
+        let tests: [(&dyn OutputConverter, IntermediateValue, Vec<&str>); 4] = [
+            (
+                &ExitStatusOutputConverter,
+                139,
+                vec![
+                    "statuses above 128 are set by a signal: 139 = 128 + 11",
+                    "which means SIGSEGV (128+11)",
+                ],
+            ),
+            (
+                &ErrnoOutputConverter,
+                -11,
+                vec![
+                    "syscalls return errors negated: errno is -(-11) = 11",
+                    "which means EAGAIN: Resource temporarily unavailable",
+                ],
+            ),
+            (
+                &NtstatusOutputConverter,
+                0xC0000005,
+                vec![
+                    "severity (bits 31:30) = 3",
+                    "customer (bit 29) = 0",
+                    "facility (bits 27:16) = 0",
+                    "code (bits 15:0) = 5",
+                    "which means STATUS_ACCESS_VIOLATION; error; facility 0; code 5",
+                ],
+            ),
+            (&HttpStatusOutputConverter, 99, vec!["not a known code"]),
+        ];
+        for (conv, input, exp) in tests {
+            assert_eq!(conv.explain(input).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn code_inconv_ok() {
+        let tests = [
+            ("EAGAIN", 11),
+            ("ewouldblock", 11),
+            ("SIGSEGV", 11),
+            ("segv", 11),
+            ("SIGRTMIN+2", 36),
+            ("RTMAX-1", 63),
+            ("EX_USAGE", 64),
+            ("E_FAIL", 0x80004005),
+            ("ERROR_ACCESS_DENIED", 5),
+            ("STATUS_ACCESS_VIOLATION", 0xC0000005),
+            ("Not Found", 404),
+            ("im a teapot", 418),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(CodeInputConverter.convert(input).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn code_inconv_err() {
+        let tests = [
+            ("", InputError::Empty),
+            ("EAGAINN", InputError::UnknownName("EAGAINN".to_string())),
+            (
+                "SIGRTMIN+31",
+                InputError::UnknownName("SIGRTMIN+31".to_string()),
+            ),
+            ("SIG", InputError::UnknownName("SIG".to_string())),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(CodeInputConverter.convert(input), Err(exp));
+        }
+    }
+}
@@ -0,0 +1,411 @@
+/// Linux errno values, aliases after the names they share a value with
+pub const ERRNO: &[(u32, &str, &str)] = &[
+    (1, "EPERM", "Operation not permitted"),
+    (2, "ENOENT", "No such file or directory"),
+    (3, "ESRCH", "No such process"),
+    (4, "EINTR", "Interrupted system call"),
+    (5, "EIO", "Input/output error"),
+    (6, "ENXIO", "No such device or address"),
+    (7, "E2BIG", "Argument list too long"),
+    (8, "ENOEXEC", "Exec format error"),
+    (9, "EBADF", "Bad file descriptor"),
+    (10, "ECHILD", "No child processes"),
+    (11, "EAGAIN", "Resource temporarily unavailable"),
+    (12, "ENOMEM", "Cannot allocate memory"),
+    (13, "EACCES", "Permission denied"),
+    (14, "EFAULT", "Bad address"),
+    (15, "ENOTBLK", "Block device required"),
+    (16, "EBUSY", "Device or resource busy"),
+    (17, "EEXIST", "File exists"),
+    (18, "EXDEV", "Invalid cross-device link"),
+    (19, "ENODEV", "No such device"),
+    (20, "ENOTDIR", "Not a directory"),
+    (21, "EISDIR", "Is a directory"),
+    (22, "EINVAL", "Invalid argument"),
+    (23, "ENFILE", "Too many open files in system"),
+    (24, "EMFILE", "Too many open files"),
+    (25, "ENOTTY", "Inappropriate ioctl for device"),
+    (26, "ETXTBSY", "Text file busy"),
+    (27, "EFBIG", "File too large"),
+    (28, "ENOSPC", "No space left on device"),
+    (29, "ESPIPE", "Illegal seek"),
+    (30, "EROFS", "Read-only file system"),
+    (31, "EMLINK", "Too many links"),
+    (32, "EPIPE", "Broken pipe"),
+    (33, "EDOM", "Numerical argument out of domain"),
+    (34, "ERANGE", "Numerical result out of range"),
+    (35, "EDEADLK", "Resource deadlock avoided"),
+    (36, "ENAMETOOLONG", "File name too long"),
+    (37, "ENOLCK", "No locks available"),
+    (38, "ENOSYS", "Function not implemented"),
+    (39, "ENOTEMPTY", "Directory not empty"),
+    (40, "ELOOP", "Too many levels of symbolic links"),
+    (42, "ENOMSG", "No message of desired type"),
+    (43, "EIDRM", "Identifier removed"),
+    (44, "ECHRNG", "Channel number out of range"),
+    (45, "EL2NSYNC", "Level 2 not synchronized"),
+    (46, "EL3HLT", "Level 3 halted"),
+    (47, "EL3RST", "Level 3 reset"),
+    (48, "ELNRNG", "Link number out of range"),
+    (49, "EUNATCH", "Protocol driver not attached"),
+    (50, "ENOCSI", "No CSI structure available"),
+    (51, "EL2HLT", "Level 2 halted"),
+    (52, "EBADE", "Invalid exchange"),
+    (53, "EBADR", "Invalid request descriptor"),
+    (54, "EXFULL", "Exchange full"),
+    (55, "ENOANO", "No anode"),
+    (56, "EBADRQC", "Invalid request code"),
+    (57, "EBADSLT", "Invalid slot"),
+    (59, "EBFONT", "Bad font file format"),
+    (60, "ENOSTR", "Device not a stream"),
+    (61, "ENODATA", "No data available"),
+    (62, "ETIME", "Timer expired"),
+    (63, "ENOSR", "Out of streams resources"),
+    (64, "ENONET", "Machine is not on the network"),
+    (65, "ENOPKG", "Package not installed"),
+    (66, "EREMOTE", "Object is remote"),
+    (67, "ENOLINK", "Link has been severed"),
+    (68, "EADV", "Advertise error"),
+    (69, "ESRMNT", "Srmount error"),
+    (70, "ECOMM", "Communication error on send"),
+    (71, "EPROTO", "Protocol error"),
+    (72, "EMULTIHOP", "Multihop attempted"),
+    (73, "EDOTDOT", "RFS specific error"),
+    (74, "EBADMSG", "Bad message"),
+    (75, "EOVERFLOW", "Value too large for defined data type"),
+    (76, "ENOTUNIQ", "Name not unique on network"),
+    (77, "EBADFD", "File descriptor in bad state"),
+    (78, "EREMCHG", "Remote address changed"),
+    (79, "ELIBACC", "Can not access a needed shared library"),
+    (80, "ELIBBAD", "Accessing a corrupted shared library"),
+    (81, "ELIBSCN", ".lib section in a.out corrupted"),
+    (
+        82,
+        "ELIBMAX",
+        "Attempting to link in too many shared libraries",
+    ),
+    (83, "ELIBEXEC", "Cannot exec a shared library directly"),
+    (
+        84,
+        "EILSEQ",
+        "Invalid or incomplete multibyte or wide character",
+    ),
+    (
+        85,
+        "ERESTART",
+        "Interrupted system call should be restarted",
+    ),
+    (86, "ESTRPIPE", "Streams pipe error"),
+    (87, "EUSERS", "Too many users"),
+    (88, "ENOTSOCK", "Socket operation on non-socket"),
+    (89, "EDESTADDRREQ", "Destination address required"),
+    (90, "EMSGSIZE", "Message too long"),
+    (91, "EPROTOTYPE", "Protocol wrong type for socket"),
+    (92, "ENOPROTOOPT", "Protocol not available"),
+    (93, "EPROTONOSUPPORT", "Protocol not supported"),
+    (94, "ESOCKTNOSUPPORT", "Socket type not supported"),
+    (95, "EOPNOTSUPP", "Operation not supported"),
+    (96, "EPFNOSUPPORT", "Protocol family not supported"),
+    (
+        97,
+        "EAFNOSUPPORT",
+        "Address family not supported by protocol",
+    ),
+    (98, "EADDRINUSE", "Address already in use"),
+    (99, "EADDRNOTAVAIL", "Cannot assign requested address"),
+    (100, "ENETDOWN", "Network is down"),
+    (101, "ENETUNREACH", "Network is unreachable"),
+    (102, "ENETRESET", "Network dropped connection on reset"),
+    (103, "ECONNABORTED", "Software caused connection abort"),
+    (104, "ECONNRESET", "Connection reset by peer"),
+    (105, "ENOBUFS", "No buffer space available"),
+    (106, "EISCONN", "Transport endpoint is already connected"),
+    (107, "ENOTCONN", "Transport endpoint is not connected"),
+    (
+        108,
+        "ESHUTDOWN",
+        "Cannot send after transport endpoint shutdown",
+    ),
+    (109, "ETOOMANYREFS", "Too many references: cannot splice"),
+    (110, "ETIMEDOUT", "Connection timed out"),
+    (111, "ECONNREFUSED", "Connection refused"),
+    (112, "EHOSTDOWN", "Host is down"),
+    (113, "EHOSTUNREACH", "No route to host"),
+    (114, "EALREADY", "Operation already in progress"),
+    (115, "EINPROGRESS", "Operation now in progress"),
+    (116, "ESTALE", "Stale file handle"),
+    (117, "EUCLEAN", "Structure needs cleaning"),
+    (118, "ENOTNAM", "Not a XENIX named type file"),
+    (119, "ENAVAIL", "No XENIX semaphores available"),
+    (120, "EISNAM", "Is a named type file"),
+    (121, "EREMOTEIO", "Remote I/O error"),
+    (122, "EDQUOT", "Disk quota exceeded"),
+    (123, "ENOMEDIUM", "No medium found"),
+    (124, "EMEDIUMTYPE", "Wrong medium type"),
+    (125, "ECANCELED", "Operation canceled"),
+    (126, "ENOKEY", "Required key not available"),
+    (127, "EKEYEXPIRED", "Key has expired"),
+    (128, "EKEYREVOKED", "Key has been revoked"),
+    (129, "EKEYREJECTED", "Key was rejected by service"),
+    (130, "EOWNERDEAD", "Owner died"),
+    (131, "ENOTRECOVERABLE", "State not recoverable"),
+    (132, "ERFKILL", "Operation not possible due to RF-kill"),
+    (133, "EHWPOISON", "Memory page has hardware error"),
+    (11, "EWOULDBLOCK", "Resource temporarily unavailable"),
+    (35, "EDEADLOCK", "Resource deadlock avoided"),
+    (95, "ENOTSUP", "Operation not supported"),
+];
+
+/// Linux standard signals, aliases after the names they share a value with
+pub const SIGNALS: &[(u32, &str, &str)] = &[
+    (1, "SIGHUP", "Hangup"),
+    (2, "SIGINT", "Interrupt"),
+    (3, "SIGQUIT", "Quit"),
+    (4, "SIGILL", "Illegal instruction"),
+    (5, "SIGTRAP", "Trace/breakpoint trap"),
+    (6, "SIGABRT", "Aborted"),
+    (7, "SIGBUS", "Bus error"),
+    (8, "SIGFPE", "Floating point exception"),
+    (9, "SIGKILL", "Killed"),
+    (10, "SIGUSR1", "User defined signal 1"),
+    (11, "SIGSEGV", "Segmentation fault"),
+    (12, "SIGUSR2", "User defined signal 2"),
+    (13, "SIGPIPE", "Broken pipe"),
+    (14, "SIGALRM", "Alarm clock"),
+    (15, "SIGTERM", "Terminated"),
+    (16, "SIGSTKFLT", "Stack fault"),
+    (17, "SIGCHLD", "Child exited"),
+    (18, "SIGCONT", "Continued"),
+    (19, "SIGSTOP", "Stopped (signal)"),
+    (20, "SIGTSTP", "Stopped"),
+    (21, "SIGTTIN", "Stopped (tty input)"),
+    (22, "SIGTTOU", "Stopped (tty output)"),
+    (23, "SIGURG", "Urgent I/O condition"),
+    (24, "SIGXCPU", "CPU time limit exceeded"),
+    (25, "SIGXFSZ", "File size limit exceeded"),
+    (26, "SIGVTALRM", "Virtual timer expired"),
+    (27, "SIGPROF", "Profiling timer expired"),
+    (28, "SIGWINCH", "Window changed"),
+    (29, "SIGIO", "I/O possible"),
+    (30, "SIGPWR", "Power failure"),
+    (31, "SIGSYS", "Bad system call"),
+    (6, "SIGIOT", "Aborted"),
+    (29, "SIGPOLL", "I/O possible"),
+];
+
+/// Range of the Linux real-time signals, SIGRTMIN to SIGRTMAX
+pub const REALTIME_SIGNALS: (u32, u32) = (34, 64);
+
+/// Process exit statuses with a conventional meaning, from shells and sysexits.h
+pub const EXIT_STATUSES: &[(u32, &str, &str)] = &[
+    (0, "EXIT_SUCCESS", "success"),
+    (1, "EXIT_FAILURE", "general error"),
+    (2, "", "misuse of shell builtins"),
+    (64, "EX_USAGE", "command line usage error"),
+    (65, "EX_DATAERR", "data format error"),
+    (66, "EX_NOINPUT", "cannot open input"),
+    (67, "EX_NOUSER", "addressee unknown"),
+    (68, "EX_NOHOST", "host name unknown"),
+    (69, "EX_UNAVAILABLE", "service unavailable"),
+    (70, "EX_SOFTWARE", "internal software error"),
+    (71, "EX_OSERR", "system error"),
+    (72, "EX_OSFILE", "critical OS file missing"),
+    (73, "EX_CANTCREAT", "can't create output file"),
+    (74, "EX_IOERR", "input/output error"),
+    (75, "EX_TEMPFAIL", "temporary failure"),
+    (76, "EX_PROTOCOL", "remote error in protocol"),
+    (77, "EX_NOPERM", "permission denied"),
+    (78, "EX_CONFIG", "configuration error"),
+    (126, "", "command found but not executable"),
+    (127, "", "command not found"),
+];
+
+/// HTTP status codes and their reason phrases
+pub const HTTP_STATUSES: &[(u32, &str)] = &[
+    (100, "Continue"),
+    (101, "Switching Protocols"),
+    (102, "Processing"),
+    (103, "Early Hints"),
+    (200, "OK"),
+    (201, "Created"),
+    (202, "Accepted"),
+    (203, "Non-Authoritative Information"),
+    (204, "No Content"),
+    (205, "Reset Content"),
+    (206, "Partial Content"),
+    (207, "Multi-Status"),
+    (208, "Already Reported"),
+    (226, "IM Used"),
+    (300, "Multiple Choices"),
+    (301, "Moved Permanently"),
+    (302, "Found"),
+    (303, "See Other"),
+    (304, "Not Modified"),
+    (305, "Use Proxy"),
+    (307, "Temporary Redirect"),
+    (308, "Permanent Redirect"),
+    (400, "Bad Request"),
+    (401, "Unauthorized"),
+    (402, "Payment Required"),
+    (403, "Forbidden"),
+    (404, "Not Found"),
+    (405, "Method Not Allowed"),
+    (406, "Not Acceptable"),
+    (407, "Proxy Authentication Required"),
+    (408, "Request Timeout"),
+    (409, "Conflict"),
+    (410, "Gone"),
+    (411, "Length Required"),
+    (412, "Precondition Failed"),
+    (413, "Content Too Large"),
+    (414, "URI Too Long"),
+    (415, "Unsupported Media Type"),
+    (416, "Range Not Satisfiable"),
+    (417, "Expectation Failed"),
+    (418, "I'm a teapot"),
+    (421, "Misdirected Request"),
+    (422, "Unprocessable Content"),
+    (423, "Locked"),
+    (424, "Failed Dependency"),
+    (425, "Too Early"),
+    (426, "Upgrade Required"),
+    (428, "Precondition Required"),
+    (429, "Too Many Requests"),
+    (431, "Request Header Fields Too Large"),
+    (451, "Unavailable For Legal Reasons"),
+    (500, "Internal Server Error"),
+    (501, "Not Implemented"),
+    (502, "Bad Gateway"),
+    (503, "Service Unavailable"),
+    (504, "Gateway Timeout"),
+    (505, "HTTP Version Not Supported"),
+    (506, "Variant Also Negotiates"),
+    (507, "Insufficient Storage"),
+    (508, "Loop Detected"),
+    (510, "Not Extended"),
+    (511, "Network Authentication Required"),
+];
+
+/// Classes of HTTP status codes, by first digit
+pub const HTTP_CLASSES: [&str; 5] = [
+    "informational",
+    "success",
+    "redirection",
+    "client error",
+    "server error",
+];
+
+/// Common HRESULT values
+pub const HRESULTS: &[(u32, &str)] = &[
+    (0x00000000, "S_OK"),
+    (0x00000001, "S_FALSE"),
+    (0x80004001, "E_NOTIMPL"),
+    (0x80004002, "E_NOINTERFACE"),
+    (0x80004003, "E_POINTER"),
+    (0x80004004, "E_ABORT"),
+    (0x80004005, "E_FAIL"),
+    (0x8000FFFF, "E_UNEXPECTED"),
+    (0x80010105, "RPC_E_SERVERFAULT"),
+    (0x800401F0, "CO_E_NOTINITIALIZED"),
+    (0x80040154, "REGDB_E_CLASSNOTREG"),
+    (0x80070005, "E_ACCESSDENIED"),
+    (0x80070006, "E_HANDLE"),
+    (0x8007000E, "E_OUTOFMEMORY"),
+    (0x80070057, "E_INVALIDARG"),
+];
+
+/// Facilities of HRESULT values
+pub const HRESULT_FACILITIES: &[(u32, &str)] = &[
+    (0, "NULL"),
+    (1, "RPC"),
+    (2, "DISPATCH"),
+    (3, "STORAGE"),
+    (4, "ITF"),
+    (7, "WIN32"),
+    (8, "WINDOWS"),
+    (9, "SSPI"),
+    (10, "CONTROL"),
+    (11, "CERT"),
+    (12, "INTERNET"),
+    (13, "MEDIASERVER"),
+    (14, "MSMQ"),
+    (15, "SETUPAPI"),
+    (16, "SCARD"),
+    (17, "COMPLUS"),
+    (18, "AAF"),
+    (19, "URT"),
+    (20, "ACS"),
+    (21, "DPLAY"),
+    (22, "UMI"),
+    (23, "SXS"),
+    (24, "WINDOWS_CE"),
+    (25, "HTTP"),
+    (32, "BACKGROUNDCOPY"),
+    (33, "CONFIGURATION"),
+    (36, "WINDOWSUPDATE"),
+    (38, "GRAPHICS"),
+    (39, "SHELL"),
+    (40, "TPM_SERVICES"),
+    (41, "TPM_SOFTWARE"),
+    (80, "WINDOWS_DEFENDER"),
+];
+
+/// Common Win32 error codes, found in HRESULT values of the WIN32 facility
+pub const WIN32_ERRORS: &[(u32, &str)] = &[
+    (0, "ERROR_SUCCESS"),
+    (1, "ERROR_INVALID_FUNCTION"),
+    (2, "ERROR_FILE_NOT_FOUND"),
+    (3, "ERROR_PATH_NOT_FOUND"),
+    (4, "ERROR_TOO_MANY_OPEN_FILES"),
+    (5, "ERROR_ACCESS_DENIED"),
+    (6, "ERROR_INVALID_HANDLE"),
+    (8, "ERROR_NOT_ENOUGH_MEMORY"),
+    (14, "ERROR_OUTOFMEMORY"),
+    (32, "ERROR_SHARING_VIOLATION"),
+    (87, "ERROR_INVALID_PARAMETER"),
+    (122, "ERROR_INSUFFICIENT_BUFFER"),
+    (183, "ERROR_ALREADY_EXISTS"),
+    (1223, "ERROR_CANCELLED"),
+    (1460, "ERROR_TIMEOUT"),
+];
+
+/// Common NTSTATUS values
+pub const NTSTATUSES: &[(u32, &str)] = &[
+    (0x00000000, "STATUS_SUCCESS"),
+    (0x00000102, "STATUS_TIMEOUT"),
+    (0x00000103, "STATUS_PENDING"),
+    (0x80000003, "STATUS_BREAKPOINT"),
+    (0x80000004, "STATUS_SINGLE_STEP"),
+    (0x80000005, "STATUS_BUFFER_OVERFLOW"),
+    (0xC0000001, "STATUS_UNSUCCESSFUL"),
+    (0xC0000002, "STATUS_NOT_IMPLEMENTED"),
+    (0xC0000005, "STATUS_ACCESS_VIOLATION"),
+    (0xC0000008, "STATUS_INVALID_HANDLE"),
+    (0xC000000D, "STATUS_INVALID_PARAMETER"),
+    (0xC0000017, "STATUS_NO_MEMORY"),
+    (0xC000001D, "STATUS_ILLEGAL_INSTRUCTION"),
+    (0xC0000022, "STATUS_ACCESS_DENIED"),
+    (0xC0000034, "STATUS_OBJECT_NAME_NOT_FOUND"),
+    (0xC000008C, "STATUS_ARRAY_BOUNDS_EXCEEDED"),
+    (0xC0000094, "STATUS_INTEGER_DIVIDE_BY_ZERO"),
+    (0xC0000096, "STATUS_PRIVILEGED_INSTRUCTION"),
+    (0xC00000FD, "STATUS_STACK_OVERFLOW"),
+    (0xC0000135, "STATUS_DLL_NOT_FOUND"),
+    (0xC0000139, "STATUS_ENTRYPOINT_NOT_FOUND"),
+    (0xC000013A, "STATUS_CONTROL_C_EXIT"),
+    (0xC0000142, "STATUS_DLL_INIT_FAILED"),
+    (0xC0000374, "STATUS_HEAP_CORRUPTION"),
+    (0xC0000409, "STATUS_STACK_BUFFER_OVERRUN"),
+];
+
+/// Facilities of NTSTATUS values
+pub const NTSTATUS_FACILITIES: &[(u32, &str)] = &[
+    (1, "DEBUGGER"),
+    (2, "RPC_RUNTIME"),
+    (3, "RPC_STUBS"),
+    (4, "IO_ERROR_CODE"),
+    (7, "NTWIN32"),
+];
+
+/// Severities of NTSTATUS values, by their two highest bits
+pub const NTSTATUS_SEVERITIES: [&str; 4] = ["success", "informational", "warning", "error"];
@@ -4,32 +4,155 @@ use strum::{EnumIter, IntoEnumIterator};
 
 use super::{
     ascii::{AsciiInputConverter, AsciiOutputConverter},
+    bcd::{
+        BcdInputConverter, BcdOutputConverter, Excess3InputConverter, Excess3OutputConverter,
+        UnpackedBcdInputConverter, UnpackedBcdOutputConverter,
+    },
     bin::{BinInputConverter, BinOutputConverter},
+    bits::BitsOutputConverter,
+    cast::CastOutputConverter,
+    codes::{
+        CodeInputConverter, ErrnoOutputConverter, ExitStatusOutputConverter,
+        HresultOutputConverter, HttpStatusOutputConverter, NtstatusOutputConverter,
+        SignalOutputConverter,
+    },
     dec::{DecInputConverter, DecOutputConverter},
+    flags::{FlagsInputConverter, FlagsOutputConverter},
+    gray::{GrayInputConverter, GrayOutputConverter},
     hex::{HexInputConverter, HexOutputConverter},
     octal::{OctInputConverter, OctOutputConverter},
+    register::{RegisterInputConverter, RegisterOutputConverter},
+    slice::MaskInputConverter,
+    symbols::{EnumOutputConverter, SymbolInputConverter},
+    unary::{
+        OneHotInputConverter, OneHotOutputConverter, ThermometerInputConverter,
+        ThermometerOutputConverter,
+    },
     InputConverter, OutputConverter,
 };
 
 /// Types of Output Converter
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, EnumIter)]
 pub enum OutputConverterType {
     DEC,
     HEX,
     BIN,
+    /// Binary reflected Gray code
+    GRAY,
+    /// Packed BCD, one decimal digit per nibble
+    BCD,
+    /// Unpacked BCD, one decimal digit per byte
+    UBCD,
+    /// Excess-3, each decimal digit plus 3 per nibble
+    XS3,
+    /// One-hot code, with only the bit of the value set
+    ONEHOT,
+    /// Thermometer code, with as many lowest bits set as the value
+    THERMO,
     OCT,
     ASCII,
+    BITS,
+    ERRNO,
+    SIGNAL,
+    EXIT,
+    HTTP,
+    HRESULT,
+    NTSTATUS,
+    /// Value cast to every primitive integer type
+    CAST,
+    /// Fields of a register declared in the config file, by name
+    REG(&'static str),
+    /// Flags of a built-in set or one declared in the config file, by name
+    FLAGS(&'static str),
+    /// Values of an enumeration declared in the config file, by name
+    ENUM(&'static str),
 }
 
 impl OutputConverterType {
     /// Get output converter of the specific type
-    pub fn get_converter(&self) -> Box<dyn OutputConverter> {
+    ///
+    /// The width in bits only applies to converters that depend on it
+    pub fn get_converter(&self, width: Option<u32>) -> Box<dyn OutputConverter> {
         match self {
             OutputConverterType::DEC => Box::new(DecOutputConverter),
             OutputConverterType::BIN => Box::new(BinOutputConverter),
+            OutputConverterType::GRAY => Box::new(GrayOutputConverter),
+            OutputConverterType::BCD => Box::new(BcdOutputConverter),
+            OutputConverterType::UBCD => Box::new(UnpackedBcdOutputConverter),
+            OutputConverterType::XS3 => Box::new(Excess3OutputConverter),
+            OutputConverterType::ONEHOT => Box::new(OneHotOutputConverter),
+            OutputConverterType::THERMO => Box::new(ThermometerOutputConverter),
             OutputConverterType::HEX => Box::new(HexOutputConverter),
             OutputConverterType::OCT => Box::new(OctOutputConverter),
             OutputConverterType::ASCII => Box::new(AsciiOutputConverter),
+            OutputConverterType::BITS => Box::new(BitsOutputConverter { width }),
+            OutputConverterType::ERRNO => Box::new(ErrnoOutputConverter),
+            OutputConverterType::SIGNAL => Box::new(SignalOutputConverter),
+            OutputConverterType::EXIT => Box::new(ExitStatusOutputConverter),
+            OutputConverterType::HTTP => Box::new(HttpStatusOutputConverter),
+            OutputConverterType::HRESULT => Box::new(HresultOutputConverter),
+            OutputConverterType::NTSTATUS => Box::new(NtstatusOutputConverter),
+            OutputConverterType::CAST => Box::new(CastOutputConverter),
+            OutputConverterType::REG(name) => Box::new(RegisterOutputConverter::named(name)),
+            OutputConverterType::FLAGS(name) => Box::new(FlagsOutputConverter::named(name)),
+            OutputConverterType::ENUM(name) => Box::new(EnumOutputConverter::named(name)),
+        }
+    }
+
+    /// Names accepted for this output converter type, canonical name first
+    ///
+    /// Registers, flag sets and enumerations are selected by their own name instead
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            Self::DEC => &["DEC", "D", "DECIMAL"],
+            Self::HEX => &["HEX", "X", "H", "HEXADECIMAL"],
+            Self::BIN => &["BIN", "B", "BINARY"],
+            Self::GRAY => &["GRAY"],
+            Self::BCD => &["BCD", "PACKED_BCD"],
+            Self::UBCD => &["UBCD", "UNPACKED_BCD"],
+            Self::XS3 => &["XS3", "EXCESS3"],
+            Self::ONEHOT => &["ONEHOT", "ONE_HOT"],
+            Self::THERMO => &["THERMO", "THERMOMETER", "UNARY"],
+            Self::OCT => &["OCT", "O", "OCTAL"],
+            Self::ASCII => &["ASCII", "CHAR"],
+            Self::BITS => &["BITS", "BREAKDOWN"],
+            Self::ERRNO => &["ERRNO"],
+            Self::SIGNAL => &["SIGNAL"],
+            Self::EXIT => &["EXIT"],
+            Self::HTTP => &["HTTP"],
+            Self::HRESULT => &["HRESULT"],
+            Self::NTSTATUS => &["NTSTATUS"],
+            Self::CAST => &["CAST", "AS"],
+            Self::REG(_) | Self::FLAGS(_) | Self::ENUM(_) => &[],
+        }
+    }
+
+    /// Radix of the digits written by this output converter type, if positional
+    pub fn radix(&self) -> Option<u32> {
+        match self {
+            Self::DEC => Some(10),
+            Self::BIN => Some(2),
+            Self::OCT => Some(8),
+            Self::HEX => Some(16),
+            Self::GRAY
+            | Self::BCD
+            | Self::UBCD
+            | Self::XS3
+            | Self::ONEHOT
+            | Self::THERMO
+            | Self::ASCII
+            | Self::BITS
+            | Self::ERRNO
+            | Self::SIGNAL
+            | Self::EXIT
+            | Self::HTTP
+            | Self::HRESULT
+            | Self::NTSTATUS
+            | Self::CAST
+            | Self::REG(_)
+            | Self::FLAGS(_)
+            | Self::ENUM(_) => None,
         }
     }
 
@@ -47,14 +170,10 @@ impl FromStr for OutputConverterType {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_ascii_uppercase().as_str() {
-            "DEC" => Ok(Self::DEC),
-            "HEX" => Ok(Self::HEX),
-            "BIN" => Ok(Self::BIN),
-            "OCT" => Ok(Self::OCT),
-            "ASCII" => Ok(Self::ASCII),
-            _ => Err(()),
-        }
+        let s = s.to_ascii_uppercase();
+        Self::iter()
+            .find(|outconv| outconv.names().contains(&s.as_str()))
+            .ok_or(())
     }
 }
 
@@ -70,37 +189,154 @@ impl<'de> Deserialize<'de> for OutputConverterType {
 }
 
 /// Types of Input Converter
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, EnumIter)]
 pub enum InputConverterType {
     DEC,
     HEX,
     BIN,
+    /// Binary reflected Gray code
+    GRAY,
+    /// Packed BCD, one decimal digit per nibble
+    BCD,
+    /// Unpacked BCD, one decimal digit per byte
+    UBCD,
+    /// Excess-3, each decimal digit plus 3 per nibble
+    XS3,
+    /// One-hot code, with only the bit of the value set
+    ONEHOT,
+    /// Thermometer code, with as many lowest bits set as the value
+    THERMO,
     OCT,
     ASCII,
+    /// Names of well-known codes, like errno values or signals
+    CODE,
+    /// Names of constants and enumeration values declared in the config file
+    SYMBOL,
+    /// Bit range whose bits are set, ex: 15:8
+    MASK,
+    /// Assignments to the fields of a register declared in the config file, by name
+    REG(&'static str),
+    /// Names of flags of a built-in set or one declared in the config file, by name
+    FLAGS(&'static str),
 }
 
 impl InputConverterType {
     /// Get input converter of the specific type
     pub fn get_converter(&self) -> Box<dyn InputConverter> {
-        match self {
-            &InputConverterType::DEC => Box::new(DecInputConverter),
-            &InputConverterType::BIN => Box::new(BinInputConverter),
-            &InputConverterType::HEX => Box::new(HexInputConverter),
-            &InputConverterType::OCT => Box::new(OctInputConverter),
-            &InputConverterType::ASCII => Box::new(AsciiInputConverter),
+        match *self {
+            InputConverterType::DEC => Box::new(DecInputConverter),
+            InputConverterType::BIN => Box::new(BinInputConverter),
+            InputConverterType::GRAY => Box::new(GrayInputConverter),
+            InputConverterType::BCD => Box::new(BcdInputConverter),
+            InputConverterType::UBCD => Box::new(UnpackedBcdInputConverter),
+            InputConverterType::XS3 => Box::new(Excess3InputConverter),
+            InputConverterType::ONEHOT => Box::new(OneHotInputConverter),
+            InputConverterType::THERMO => Box::new(ThermometerInputConverter),
+            InputConverterType::HEX => Box::new(HexInputConverter),
+            InputConverterType::OCT => Box::new(OctInputConverter),
+            InputConverterType::ASCII => Box::new(AsciiInputConverter),
+            InputConverterType::CODE => Box::new(CodeInputConverter),
+            InputConverterType::SYMBOL => Box::new(SymbolInputConverter::default()),
+            InputConverterType::MASK => Box::new(MaskInputConverter),
+            InputConverterType::REG(name) => Box::new(RegisterInputConverter::named(name)),
+            InputConverterType::FLAGS(name) => Box::new(FlagsInputConverter::named(name)),
         }
     }
 }
 
 impl InputConverterType {
+    /// Names accepted for this input converter type, canonical name first
+    ///
+    /// Registers and flag sets are selected by their own name instead
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            Self::DEC => &["DEC", "D", "DECIMAL"],
+            Self::HEX => &["HEX", "X", "H", "HEXADECIMAL"],
+            Self::BIN => &["BIN", "B", "BINARY"],
+            Self::GRAY => &["GRAY"],
+            Self::BCD => &["BCD", "PACKED_BCD"],
+            Self::UBCD => &["UBCD", "UNPACKED_BCD"],
+            Self::XS3 => &["XS3", "EXCESS3"],
+            Self::ONEHOT => &["ONEHOT", "ONE_HOT"],
+            Self::THERMO => &["THERMO", "THERMOMETER", "UNARY"],
+            Self::OCT => &["OCT", "O", "OCTAL"],
+            Self::ASCII => &["ASCII", "CHAR"],
+            Self::CODE => &["CODE"],
+            Self::SYMBOL => &["SYMBOL", "SYM", "CONST"],
+            Self::MASK => &["MASK"],
+            Self::REG(_) | Self::FLAGS(_) => &[],
+        }
+    }
+
+    /// Radix of the digits read by this input converter type, if positional
+    pub fn radix(&self) -> Option<u32> {
+        match self {
+            Self::DEC => Some(10),
+            Self::BIN => Some(2),
+            Self::OCT => Some(8),
+            Self::HEX => Some(16),
+            Self::GRAY
+            | Self::BCD
+            | Self::UBCD
+            | Self::XS3
+            | Self::ONEHOT
+            | Self::THERMO
+            | Self::ASCII
+            | Self::CODE
+            | Self::SYMBOL
+            | Self::MASK
+            | Self::REG(_)
+            | Self::FLAGS(_) => None,
+        }
+    }
+
+    /// Detect the input converter type named by a base prefix or suffix, if it accepts the input
+    ///
+    /// Ex: 0x1F, 1Fh, 0b101, 0o17, but not 0bad, which is only hexadecimal
+    pub fn from_notation(input: &str) -> Option<Self> {
+        // Suffix first, as in 0B1h the prefix is made of hexadecimal digits
+        if let Some(digits) = input.strip_suffix(['h', 'H']) {
+            if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Some(Self::HEX);
+            }
+        }
+
+        let inconv = match input.get(..2).map(str::to_ascii_lowercase).as_deref() {
+            Some("0x") => Self::HEX,
+            Some("0b") => Self::BIN,
+            Some("0o") => Self::OCT,
+            _ => return None,
+        };
+        inconv
+            .get_converter()
+            .convert(input)
+            .is_ok()
+            .then_some(inconv)
+    }
+
+    /// Check if this interpretation is unlikely for a value that is also a valid decimal number
+    pub fn is_unlikely_besides_decimal(&self) -> bool {
+        matches!(self, Self::BIN | Self::OCT)
+    }
+
     /// Check if an output converter should not be paired with this input converter
     pub fn is_outconv_excluded(&self, outconv: &OutputConverterType) -> bool {
         match self {
             Self::BIN => outconv == &OutputConverterType::BIN,
+            Self::GRAY => outconv == &OutputConverterType::GRAY,
+            Self::BCD => outconv == &OutputConverterType::BCD,
+            Self::UBCD => outconv == &OutputConverterType::UBCD,
+            Self::XS3 => outconv == &OutputConverterType::XS3,
+            Self::ONEHOT => outconv == &OutputConverterType::ONEHOT,
+            Self::THERMO => outconv == &OutputConverterType::THERMO,
             Self::DEC => outconv == &OutputConverterType::DEC,
             Self::HEX => outconv == &OutputConverterType::HEX,
             Self::OCT => outconv == &OutputConverterType::OCT,
             Self::ASCII => outconv == &OutputConverterType::ASCII,
+            Self::CODE | Self::SYMBOL | Self::MASK => false,
+            Self::REG(name) => outconv == &OutputConverterType::REG(name),
+            Self::FLAGS(name) => outconv == &OutputConverterType::FLAGS(name),
         }
     }
 }
@@ -109,14 +345,10 @@ impl FromStr for InputConverterType {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_ascii_uppercase().as_str() {
-            "DEC" => Ok(Self::DEC),
-            "HEX" => Ok(Self::HEX),
-            "BIN" => Ok(Self::BIN),
-            "OCT" => Ok(Self::OCT),
-            "ASCII" => Ok(Self::ASCII),
-            _ => Err(()),
-        }
+        let s = s.to_ascii_uppercase();
+        Self::iter()
+            .find(|inconv| inconv.names().contains(&s.as_str()))
+            .ok_or(())
     }
 }
 
@@ -130,3 +362,79 @@ impl<'de> Deserialize<'de> for InputConverterType {
             .map_err(|_| de::Error::custom(format!("Invalid Ouptut Converter type: {}", s)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outconv_from_str_ok() {
+        let tests = [
+            ("hex", OutputConverterType::HEX),
+            ("X", OutputConverterType::HEX),
+            ("Hexadecimal", OutputConverterType::HEX),
+            ("d", OutputConverterType::DEC),
+            ("b", OutputConverterType::BIN),
+            ("octal", OutputConverterType::OCT),
+            ("char", OutputConverterType::ASCII),
+            ("Bits", OutputConverterType::BITS),
+            ("as", OutputConverterType::CAST),
+            ("gray", OutputConverterType::GRAY),
+            ("packed_bcd", OutputConverterType::BCD),
+            ("Excess3", OutputConverterType::XS3),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(input.parse::<OutputConverterType>().unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn inconv_from_str_ok() {
+        let tests = [
+            ("DEC", InputConverterType::DEC),
+            ("decimal", InputConverterType::DEC),
+            ("h", InputConverterType::HEX),
+            ("o", InputConverterType::OCT),
+            ("CHAR", InputConverterType::ASCII),
+            ("mask", InputConverterType::MASK),
+            ("ubcd", InputConverterType::UBCD),
+            ("one_hot", InputConverterType::ONEHOT),
+            ("unary", InputConverterType::THERMO),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(input.parse::<InputConverterType>().unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn inconv_from_notation_ok() {
+        let tests = [
+            ("0x10", Some(InputConverterType::HEX)),
+            ("0XfF", Some(InputConverterType::HEX)),
+            ("10h", Some(InputConverterType::HEX)),
+            ("0b11h", Some(InputConverterType::HEX)),
+            ("0b11", Some(InputConverterType::BIN)),
+            ("0o17", Some(InputConverterType::OCT)),
+            ("0b", None),
+            ("0bad", None),
+            ("0B1F", None),
+            ("0x", None),
+            ("10", None),
+            ("h", None),
+            ("0", None),
+            ("é", None),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(InputConverterType::from_notation(input), exp);
+        }
+    }
+
+    #[test]
+    fn conv_from_str_err() {
+        let tests = ["", "hx", "decimals", "c", "-h"];
+        for input in tests {
+            input.parse::<InputConverterType>().unwrap_err();
+            input.parse::<OutputConverterType>().unwrap_err();
+        }
+    }
+}
@@ -2,23 +2,23 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::digit1,
-    combinator::{map, map_res},
+    combinator::{cut, map, map_res},
     sequence::preceded,
     IResult,
 };
 
-use super::{InputConverter, IntermediateValue, OutputConverter};
+use super::{InputConverter, InputError, IntermediateValue, OutputConverter, OutputError};
 
 /// Input converter that accepts decimal numbers as inputs
 pub struct DecInputConverter;
 
 impl InputConverter for DecInputConverter {
-    fn convert(&self, input: &str) -> Result<IntermediateValue, ()> {
-        let (input, val) = parse_dec_int(input).map_err(|_| ())?;
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        let (rest, val) = parse_dec_int(input).map_err(|e| InputError::from_nom(input, e))?;
 
         // Check if there are unconsumed characters
-        if input.len() > 0 {
-            return Err(());
+        if !rest.is_empty() {
+            return Err(InputError::trailing(input, rest));
         }
 
         Ok(val)
@@ -29,8 +29,8 @@ impl InputConverter for DecInputConverter {
 pub struct DecOutputConverter;
 
 impl OutputConverter for DecOutputConverter {
-    fn convert(&self, input: IntermediateValue) -> Result<String, ()> {
-        return Ok(format!("{}", input));
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        Ok(format!("{}", input))
     }
 }
 
@@ -41,7 +41,7 @@ fn parse_dec_int(input: &str) -> IResult<&str, IntermediateValue> {
 
 /// Parse negative decimal integer
 fn parse_neg_dec_int(input: &str) -> IResult<&str, IntermediateValue> {
-    map(preceded(tag("-"), parse_pos_dec_int), |val| -val)(input)
+    map(preceded(tag("-"), cut(parse_pos_dec_int)), |val| -val)(input)
 }
 
 /// Parse positive decimal integer
@@ -66,10 +66,25 @@ mod tests {
 
     #[test]
     fn dec_inconv_err() {
-        let tests = ["", "abcde", "123abc", "-+ciao", "-"];
+        let tests = [
+            ("", InputError::Empty),
+            ("abcde", InputError::InvalidCharacter(0, 'a')),
+            ("123abc", InputError::InvalidCharacter(3, 'a')),
+            ("-+ciao", InputError::InvalidCharacter(1, '+')),
+            ("-", InputError::EmptyAfterPrefix("-".to_string())),
+            (
+                "1000000000000000000000000000000000000000",
+                InputError::Overflow,
+            ),
+            (
+                "-1000000000000000000000000000000000000000",
+                InputError::Overflow,
+            ),
+            ("€5", InputError::InvalidCharacter(0, '€')),
+        ];
         let conv = DecInputConverter;
-        for input in tests {
-            conv.convert(input).unwrap_err();
+        for (input, exp) in tests {
+            assert_eq!(conv.convert(input).unwrap_err(), exp);
         }
     }
 
@@ -0,0 +1,468 @@
+use serde::Deserialize;
+use std::{collections::HashMap, sync::OnceLock};
+
+use super::{
+    AlreadyInUse, InputConverter, InputConverterType, InputError, IntermediateValue,
+    OutputConverter, OutputError,
+};
+
+/// Flag sets in use, set once at startup
+static FLAG_SETS: OnceLock<FlagSets> = OnceLock::new();
+
+/// Flags of sets that are not declared
+static EMPTY_SET: FlagSet = FlagSet {
+    flags: Vec::new(),
+    octal: false,
+};
+
+/// Flags of open(2), with the access mode taking the two lowest bits
+const OPEN_FLAGS: [(&str, u128, u128); 20] = [
+    ("O_RDONLY", 0, 0o3),
+    ("O_WRONLY", 0o1, 0o3),
+    ("O_RDWR", 0o2, 0o3),
+    ("O_CREAT", 0o100, 0o100),
+    ("O_EXCL", 0o200, 0o200),
+    ("O_NOCTTY", 0o400, 0o400),
+    ("O_TRUNC", 0o1000, 0o1000),
+    ("O_APPEND", 0o2000, 0o2000),
+    ("O_NONBLOCK", 0o4000, 0o4000),
+    ("O_DSYNC", 0o10000, 0o10000),
+    ("O_ASYNC", 0o20000, 0o20000),
+    ("O_DIRECT", 0o40000, 0o40000),
+    ("O_LARGEFILE", 0o100000, 0o100000),
+    ("O_DIRECTORY", 0o200000, 0o200000),
+    ("O_NOFOLLOW", 0o400000, 0o400000),
+    ("O_NOATIME", 0o1000000, 0o1000000),
+    ("O_CLOEXEC", 0o2000000, 0o2000000),
+    ("O_SYNC", 0o4010000, 0o4010000),
+    ("O_PATH", 0o10000000, 0o10000000),
+    ("O_TMPFILE", 0o20200000, 0o20200000),
+];
+
+/// Memory protection flags of mmap(2) and mprotect(2)
+const PROT_FLAGS: [(&str, u128, u128); 7] = [
+    ("PROT_NONE", 0, 0),
+    ("PROT_READ", 0x1, 0x1),
+    ("PROT_WRITE", 0x2, 0x2),
+    ("PROT_EXEC", 0x4, 0x4),
+    ("PROT_SEM", 0x8, 0x8),
+    ("PROT_GROWSDOWN", 0x01000000, 0x01000000),
+    ("PROT_GROWSUP", 0x02000000, 0x02000000),
+];
+
+/// File type and mode bits of stat(2), the permissions being left in octal
+const MODE_FLAGS: [(&str, u128, u128); 10] = [
+    ("S_IFSOCK", 0o140000, 0o170000),
+    ("S_IFLNK", 0o120000, 0o170000),
+    ("S_IFREG", 0o100000, 0o170000),
+    ("S_IFBLK", 0o060000, 0o170000),
+    ("S_IFDIR", 0o040000, 0o170000),
+    ("S_IFCHR", 0o020000, 0o170000),
+    ("S_IFIFO", 0o010000, 0o170000),
+    ("S_ISUID", 0o4000, 0o4000),
+    ("S_ISGID", 0o2000, 0o2000),
+    ("S_ISVTX", 0o1000, 0o1000),
+];
+
+/// Set the flag sets available to the converters
+///
+/// Fails after the flag sets have been used
+pub fn set_flag_sets(flag_sets: FlagSets) -> Result<(), AlreadyInUse> {
+    FLAG_SETS
+        .set(flag_sets)
+        .map_err(|_| AlreadyInUse("flag sets"))
+}
+
+/// Get the flag sets in use, the built-in ones if none were set
+pub fn flag_sets() -> &'static FlagSets {
+    FLAG_SETS.get_or_init(FlagSets::builtin)
+}
+
+/// Flag sets declared in the config file, by name
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+pub struct FlagSets(HashMap<String, FlagSet>);
+
+impl FlagSets {
+    /// Built-in sets of Linux flags: open, prot and mode
+    pub fn builtin() -> Self {
+        Self(HashMap::from([
+            ("open".to_string(), FlagSet::from_table(&OPEN_FLAGS, false)),
+            ("prot".to_string(), FlagSet::from_table(&PROT_FLAGS, false)),
+            ("mode".to_string(), FlagSet::from_table(&MODE_FLAGS, true)),
+        ]))
+    }
+
+    /// Find a flag set by name, ignoring case if there is no exact match
+    pub fn get(&self, name: &str) -> Option<(&str, &FlagSet)> {
+        self.0
+            .get_key_value(name)
+            .or_else(|| {
+                self.0
+                    .iter()
+                    .find(|(set, _)| set.eq_ignore_ascii_case(name))
+            })
+            .map(|(set, flags)| (set.as_str(), flags))
+    }
+
+    /// Get the names of all flag sets
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// Add the flag sets of another collection, replacing those with the same name
+    pub fn merge(&mut self, other: FlagSets) {
+        self.0.extend(other.0);
+    }
+}
+
+/// Named flags that make up a value
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+#[serde(try_from = "HashMap<String, FlagConfig>")]
+pub struct FlagSet {
+    pub flags: Vec<Flag>,
+    /// Write leftover bits in octal instead of hexadecimal, as for file permissions
+    pub octal: bool,
+}
+
+/// Named value of the bits of a mask
+///
+/// Single bit flags are their own mask. Flags with a 0 mask only match 0
+#[derive(Debug, PartialEq, Clone)]
+pub struct Flag {
+    pub name: String,
+    pub value: u128,
+    pub mask: u128,
+}
+
+/// Flag as written in the config file. Ex: 4 or { value = 0, mask = 3 }
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FlagConfig {
+    Value(u64),
+    Masked { value: u64, mask: u64 },
+}
+
+impl TryFrom<HashMap<String, FlagConfig>> for FlagSet {
+    type Error = String;
+
+    fn try_from(value: HashMap<String, FlagConfig>) -> Result<Self, Self::Error> {
+        let mut flags = value
+            .into_iter()
+            .map(|(name, flag)| {
+                let (value, mask): (u128, u128) = match flag {
+                    FlagConfig::Value(value) => (value.into(), value.into()),
+                    FlagConfig::Masked { value, mask } => (value.into(), mask.into()),
+                };
+                if value & !mask != 0 {
+                    return Err(format!("Value of flag {} outside of its mask", name));
+                }
+                Ok(Flag { name, value, mask })
+            })
+            .collect::<Result<Vec<Flag>, String>>()?;
+
+        flags.sort_by(|a, b| a.value.cmp(&b.value).then(a.name.cmp(&b.name)));
+        Ok(Self {
+            flags,
+            octal: false,
+        })
+    }
+}
+
+impl FlagSet {
+    /// Build flag set from a table of names, values and masks
+    fn from_table(table: &[(&str, u128, u128)], octal: bool) -> Self {
+        Self {
+            flags: table
+                .iter()
+                .map(|(name, value, mask)| Flag {
+                    name: name.to_string(),
+                    value: *value,
+                    mask: *mask,
+                })
+                .collect(),
+            octal,
+        }
+    }
+
+    /// Get flag set by name, empty if it is not declared
+    fn named(name: &str) -> &'static Self {
+        flag_sets().get(name).map_or(&EMPTY_SET, |(_, set)| set)
+    }
+
+    /// Find the flags that make up a value, in the order of the set, and the bits left over
+    ///
+    /// Flags with wider masks are matched first, so that they take precedence
+    /// over the single bit flags they contain
+    pub fn decompose(&self, value: u128) -> (Vec<&Flag>, u128) {
+        let mut by_width: Vec<(usize, &Flag)> = self.flags.iter().enumerate().collect();
+        by_width.sort_by_key(|(_, flag)| std::cmp::Reverse(flag.mask.count_ones()));
+
+        let mut claimed = 0;
+        let mut matched = vec![];
+        for (index, flag) in by_width {
+            let matches = if flag.mask == 0 {
+                value == 0
+            } else {
+                flag.mask & claimed == 0 && value & flag.mask == flag.value
+            };
+            if matches {
+                claimed |= flag.mask;
+                matched.push((index, flag));
+            }
+        }
+
+        matched.sort_by_key(|(index, _)| *index);
+        (
+            matched.into_iter().map(|(_, flag)| flag).collect(),
+            value & !claimed,
+        )
+    }
+
+    /// Write leftover bits, in octal with a leading 0 or in hexadecimal
+    pub fn format_rest(&self, rest: u128) -> String {
+        if self.octal && rest == 0 {
+            "0".to_string()
+        } else if self.octal {
+            format!("0{:o}", rest)
+        } else {
+            format!("0x{:X}", rest)
+        }
+    }
+
+    /// Find a flag by name, ignoring case
+    fn flag(&self, name: &str) -> Option<&Flag> {
+        self.flags
+            .iter()
+            .find(|flag| flag.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Input converter that combines flag names. Ex: O_CREAT|O_RDWR, S_IFREG|0644
+///
+/// Numbers are read as in C, with a leading 0 meaning octal
+pub struct FlagsInputConverter<'a> {
+    pub set: &'a FlagSet,
+}
+
+impl FlagsInputConverter<'static> {
+    /// Get converter for a built-in flag set or one declared in the config file
+    pub fn named(name: &str) -> Self {
+        Self {
+            set: FlagSet::named(name),
+        }
+    }
+}
+
+impl InputConverter for FlagsInputConverter<'_> {
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        let mut value = 0;
+        for term in input.split('|').map(str::trim) {
+            if term.is_empty() {
+                return Err(InputError::Empty);
+            }
+            value |= match self.set.flag(term) {
+                Some(flag) => flag.value,
+                None => {
+                    parse_number(term).ok_or_else(|| InputError::UnknownFlag(term.to_string()))?
+                }
+            };
+        }
+
+        IntermediateValue::try_from(value).map_err(|_| InputError::Overflow)
+    }
+}
+
+/// Output converter that decomposes a value into named flags
+///
+/// Ex: O_WRONLY|O_CREAT|O_TRUNC, with unknown bits at the end
+pub struct FlagsOutputConverter<'a> {
+    pub set: &'a FlagSet,
+}
+
+impl FlagsOutputConverter<'static> {
+    /// Get converter for a built-in flag set or one declared in the config file
+    pub fn named(name: &str) -> Self {
+        Self {
+            set: FlagSet::named(name),
+        }
+    }
+}
+
+impl OutputConverter for FlagsOutputConverter<'_> {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        // Don't decode negative values
+        if input < 0 {
+            return Err(OutputError::Negative);
+        }
+
+        let (flags, rest) = self.set.decompose(input as u128);
+        let mut parts: Vec<String> = flags.iter().map(|flag| flag.name.clone()).collect();
+        if rest != 0 || parts.is_empty() {
+            parts.push(self.set.format_rest(rest));
+        }
+        Ok(parts.join("|"))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        if input < 0 {
+            return Some(vec![OutputError::Negative.to_string()]);
+        }
+
+        let (flags, rest) = self.set.decompose(input as u128);
+        let mut steps: Vec<String> = flags
+            .iter()
+            .map(|flag| match flag.mask {
+                0 => format!("{}: no bit is set", flag.name),
+                mask => format!(
+                    "{}: 0x{:X} & 0x{:X} = 0x{:X}",
+                    flag.name, input, mask, flag.value
+                ),
+            })
+            .collect();
+        if rest != 0 {
+            steps.push(format!("bits left over: {}", self.set.format_rest(rest)));
+        }
+        Some(steps)
+    }
+}
+
+/// Parse number as written in C, or with a base notation. Ex: 0644, 0x1F, 0b101
+fn parse_number(term: &str) -> Option<u128> {
+    let inconv = match InputConverterType::from_notation(term) {
+        Some(inconv) => inconv,
+        None if term.len() > 1 && term.starts_with('0') => InputConverterType::OCT,
+        None => InputConverterType::DEC,
+    };
+    inconv
+        .get_converter()
+        .convert(term)
+        .ok()
+        .and_then(|value| u128::try_from(value).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builtin(name: &str) -> FlagSet {
+        FlagSets::builtin().get(name).unwrap().1.clone()
+    }
+
+    #[test]
+    fn flags_outconv_ok() {
+        let tests = [
+            ("open", 0o1101, "O_WRONLY|O_CREAT|O_TRUNC"),
+            ("open", 0, "O_RDONLY"),
+            ("open", 0o4010002, "O_RDWR|O_SYNC"),
+            ("open", 0o20200000, "O_RDONLY|O_TMPFILE"),
+            ("open", 0o40000003, "0x800003"),
+            ("prot", 0x5, "PROT_READ|PROT_EXEC"),
+            ("prot", 0, "PROT_NONE"),
+            ("prot", 0x30, "0x30"),
+            ("prot", 0x13, "PROT_READ|PROT_WRITE|0x10"),
+            ("mode", 0o100644, "S_IFREG|0644"),
+            ("mode", 0o44755, "S_IFDIR|S_ISUID|0755"),
+            ("mode", 0o120777, "S_IFLNK|0777"),
+            ("mode", 0, "0"),
+        ];
+        for (set, input, exp) in tests {
+            let set = builtin(set);
+            let converter = FlagsOutputConverter { set: &set };
+            assert_eq!(converter.convert(input).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn flags_outconv_err() {
+        let set = builtin("open");
+        let converter = FlagsOutputConverter { set: &set };
+        assert_eq!(converter.convert(-1), Err(OutputError::Negative));
+    }
+
+    #[test]
+    fn flags_outconv_explain() {
+        let set = builtin("open");
+        let converter = FlagsOutputConverter { set: &set };
+        assert_eq!(
+            converter.explain(0x800241).unwrap(),
+            vec![
+                "O_WRONLY: 0x800241 & 0x3 = 0x1",
+                "O_CREAT: 0x800241 & 0x40 = 0x40",
+                "O_TRUNC: 0x800241 & 0x200 = 0x200",
+                "bits left over: 0x800000",
+            ]
+        );
+        let set = builtin("prot");
+        let converter = FlagsOutputConverter { set: &set };
+        assert_eq!(
+            converter.explain(0).unwrap(),
+            vec!["PROT_NONE: no bit is set"]
+        );
+    }
+
+    #[test]
+    fn flags_inconv_ok() {
+        let tests = [
+            ("open", "O_CREAT|O_RDWR", 0o102),
+            ("open", "o_wronly | o_creat | o_trunc", 0o1101),
+            ("open", "O_RDONLY", 0),
+            ("prot", "PROT_READ|0x10", 0x11),
+            ("mode", "S_IFREG|0644", 0o100644),
+            ("mode", "0755", 0o755),
+            ("mode", "0", 0),
+        ];
+        for (set, input, exp) in tests {
+            let set = builtin(set);
+            let converter = FlagsInputConverter { set: &set };
+            assert_eq!(converter.convert(input).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn flags_inconv_err() {
+        let set = builtin("open");
+        let converter = FlagsInputConverter { set: &set };
+        let tests = [
+            ("", InputError::Empty),
+            ("O_CREAT|", InputError::Empty),
+            ("O_CREATE", InputError::UnknownFlag("O_CREATE".to_string())),
+            ("O_CREAT|089", InputError::UnknownFlag("089".to_string())),
+            ("-1", InputError::UnknownFlag("-1".to_string())),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(converter.convert(input), Err(exp));
+        }
+    }
+
+    #[test]
+    fn flag_set_deserialize_ok() {
+        let set: FlagSet = toml::from_str(
+            r#"
+            READY = 1
+            ERROR = 4
+            MODE_B = { value = 16, mask = 48 }
+            MODE_A = { value = 0, mask = 48 }
+            "#,
+        )
+        .unwrap();
+        let names: Vec<&str> = set.flags.iter().map(|flag| flag.name.as_str()).collect();
+        assert_eq!(names, vec!["MODE_A", "READY", "ERROR", "MODE_B"]);
+
+        let converter = FlagsOutputConverter { set: &set };
+        assert_eq!(converter.convert(0x15).unwrap(), "READY|ERROR|MODE_B");
+        assert_eq!(converter.convert(0x21).unwrap(), "READY|0x20");
+    }
+
+    #[test]
+    fn flag_set_deserialize_err() {
+        let tests = [
+            "READY = -1",
+            "READY = \"1\"",
+            "MODE = { value = 3, mask = 1 }",
+            "MODE = { value = 3 }",
+        ];
+        for input in tests {
+            toml::from_str::<FlagSet>(input).unwrap_err();
+        }
+    }
+}
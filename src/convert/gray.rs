@@ -0,0 +1,102 @@
+use super::{
+    bin::BinInputConverter, InputConverter, InputError, IntermediateValue, OutputConverter,
+    OutputError,
+};
+
+/// Input converter that accepts binary reflected Gray codes as inputs
+pub struct GrayInputConverter;
+
+impl InputConverter for GrayInputConverter {
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        let gray = BinInputConverter.convert(input)?;
+
+        // Each bit is the XOR of the Gray code bits at and above it
+        let mut value = gray;
+        let mut shift = 1;
+        while shift < IntermediateValue::BITS {
+            value ^= value >> shift;
+            shift <<= 1;
+        }
+        Ok(value)
+    }
+}
+
+/// Output converter that gives binary reflected Gray codes as outputs
+pub struct GrayOutputConverter;
+
+impl OutputConverter for GrayOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        // Don't encode negative values
+        if input < 0 {
+            return Err(OutputError::Negative);
+        }
+        Ok(format!("{:b}", input ^ input >> 1))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        Some(match self.convert(input) {
+            Ok(gray) => vec![format!("{:b} ^ {:b} = {}", input, input >> 1, gray)],
+            Err(err) => vec![err.to_string()],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gray_inconv_ok() {
+        let tests = [
+            ("0", 0),
+            ("1", 1),
+            ("11", 2),
+            ("10", 3),
+            ("0b110", 4),
+            ("1000", 15),
+            ("11000000", 128),
+        ];
+        let conv = GrayInputConverter;
+        for (input, exp) in tests {
+            assert_eq!(conv.convert(input).unwrap(), exp);
+        }
+        assert_eq!(
+            conv.convert("102"),
+            Err(InputError::InvalidCharacter(2, '2'))
+        );
+    }
+
+    #[test]
+    fn gray_outconv_ok() {
+        let tests = [
+            (0, "0"),
+            (2, "11"),
+            (7, "100"),
+            (15, "1000"),
+            (128, "11000000"),
+        ];
+        let conv = GrayOutputConverter;
+        for (input, exp) in tests {
+            assert_eq!(conv.convert(input).unwrap(), exp);
+        }
+        assert_eq!(conv.convert(-1), Err(OutputError::Negative));
+    }
+
+    #[test]
+    fn gray_outconv_explain() {
+        let conv = GrayOutputConverter;
+        assert_eq!(conv.explain(7).unwrap(), vec!["111 ^ 11 = 100"]);
+        assert_eq!(
+            conv.explain(-1).unwrap(),
+            vec!["negative value has no unsigned representation"]
+        );
+    }
+
+    #[test]
+    fn gray_round_trip() {
+        for value in 0..1024 {
+            let gray = GrayOutputConverter.convert(value).unwrap();
+            assert_eq!(GrayInputConverter.convert(&gray).unwrap(), value);
+        }
+    }
+}
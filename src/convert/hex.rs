@@ -1,25 +1,24 @@
 use nom::{
+    branch::alt,
     bytes::complete::tag_no_case,
     character::complete::hex_digit1,
-    combinator::{map_res, opt},
-    sequence::preceded,
+    combinator::{cut, map_res},
+    sequence::{preceded, terminated},
     IResult,
 };
 
-// TODO: add -h postfix. Ex: 23h
-
-use super::{InputConverter, IntermediateValue, OutputConverter};
+use super::{InputConverter, InputError, IntermediateValue, OutputConverter, OutputError};
 
 /// Input converter that accepts hexadecimal numbers as inputs
 pub struct HexInputConverter;
 
 impl InputConverter for HexInputConverter {
-    fn convert(&self, input: &str) -> Result<IntermediateValue, ()> {
-        let (input, val) = parse_pos_hex_int(input).map_err(|_| ())?;
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        let (rest, val) = parse_pos_hex_int(input).map_err(|e| InputError::from_nom(input, e))?;
 
         // Check if there are unconsumed characters
-        if input.len() > 0 {
-            return Err(());
+        if !rest.is_empty() {
+            return Err(InputError::trailing(input, rest));
         }
 
         Ok(val)
@@ -30,20 +29,25 @@ impl InputConverter for HexInputConverter {
 pub struct HexOutputConverter;
 
 impl OutputConverter for HexOutputConverter {
-    fn convert(&self, input: IntermediateValue) -> Result<String, ()> {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
         // Don't encode negative values
         if input < 0 {
-            return Err(());
+            return Err(OutputError::Negative);
         }
-        return Ok(format!("{:X}", input));
+        Ok(format!("{:X}", input))
     }
 }
 
-/// Parse positive hexadecimal integer
+/// Parse positive hexadecimal integer, with optional 0x prefix or h suffix
 fn parse_pos_hex_int(input: &str) -> IResult<&str, IntermediateValue> {
-    map_res(preceded(opt(tag_no_case("0x")), hex_digit1), |digits| {
-        IntermediateValue::from_str_radix(digits, 16)
-    })(input)
+    map_res(
+        alt((
+            preceded(tag_no_case("0x"), cut(hex_digit1)),
+            terminated(hex_digit1, tag_no_case("h")),
+            hex_digit1,
+        )),
+        |digits| IntermediateValue::from_str_radix(digits, 16),
+    )(input)
 }
 
 #[cfg(test)]
@@ -60,6 +64,8 @@ mod tests {
             ("00000", 0),
             ("0", 0),
             ("0x0", 0),
+            ("23h", 0x23),
+            ("0b11H", 0xB11),
         ];
         let conv = HexInputConverter;
         for (input, exp) in tests {
@@ -69,10 +75,21 @@ mod tests {
 
     #[test]
     fn hex_inconv_err() {
-        let tests = ["", "abcdefg", "+*&#", "0x", "aBC0x1234"];
+        let tests = [
+            ("", InputError::Empty),
+            ("abcdefg", InputError::InvalidCharacter(6, 'g')),
+            ("+*&#", InputError::InvalidCharacter(0, '+')),
+            ("0x", InputError::EmptyAfterPrefix("0x".to_string())),
+            ("aBC0x1234", InputError::InvalidCharacter(4, 'x')),
+            ("h", InputError::InvalidCharacter(0, 'h')),
+            ("0x23h", InputError::InvalidCharacter(4, 'h')),
+            ("23hh", InputError::InvalidCharacter(3, 'h')),
+            ("0xZZ", InputError::InvalidCharacter(2, 'Z')),
+            (&"F".repeat(33), InputError::Overflow),
+        ];
         let conv = HexInputConverter;
-        for input in tests {
-            conv.convert(input).unwrap_err();
+        for (input, exp) in tests {
+            assert_eq!(conv.convert(input).unwrap_err(), exp);
         }
     }
 
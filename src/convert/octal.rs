@@ -6,18 +6,18 @@ use nom::{
     IResult, InputTakeAtPosition,
 };
 
-use super::{InputConverter, IntermediateValue, OutputConverter};
+use super::{InputConverter, InputError, IntermediateValue, OutputConverter, OutputError};
 
 /// Input converter that accepts octal numbers as inputs
 pub struct OctInputConverter;
 
 impl InputConverter for OctInputConverter {
-    fn convert(&self, input: &str) -> Result<IntermediateValue, ()> {
-        let (input, val) = parse_pos_oct_int(input).map_err(|_| ())?;
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        let (rest, val) = parse_pos_oct_int(input).map_err(|e| InputError::from_nom(input, e))?;
 
         // Check if there are unconsumed characters
-        if input.len() > 0 {
-            return Err(());
+        if !rest.is_empty() {
+            return Err(InputError::trailing(input, rest));
         }
 
         Ok(val)
@@ -28,12 +28,12 @@ impl InputConverter for OctInputConverter {
 pub struct OctOutputConverter;
 
 impl OutputConverter for OctOutputConverter {
-    fn convert(&self, input: IntermediateValue) -> Result<String, ()> {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
         // Don't encode negative values
         if input < 0 {
-            return Err(());
+            return Err(OutputError::Negative);
         }
-        return Ok(format!("{:o}", input));
+        Ok(format!("{:o}", input))
     }
 }
 
@@ -46,7 +46,7 @@ fn parse_pos_oct_int(input: &str) -> IResult<&str, IntermediateValue> {
 
 /// Parses at least one binary digit
 fn oct_digit1(input: &str) -> IResult<&str, &str> {
-    input.split_at_position1_complete(|item| !(item >= '0' && item <= '7'), ErrorKind::Digit)
+    input.split_at_position1_complete(|item| !('0'..='7').contains(&item), ErrorKind::Digit)
 }
 
 #[cfg(test)]
@@ -72,10 +72,16 @@ mod tests {
 
     #[test]
     fn oct_inconv_err() {
-        let tests = ["", "abcdefg", "8765", "0o", "0b10010"];
+        let tests = [
+            ("", InputError::Empty),
+            ("abcdefg", InputError::InvalidCharacter(0, 'a')),
+            ("8765", InputError::InvalidCharacter(0, '8')),
+            ("0o", InputError::EmptyAfterPrefix("0o".to_string())),
+            ("0b10010", InputError::InvalidCharacter(1, 'b')),
+        ];
         let conv = OctInputConverter;
-        for input in tests {
-            conv.convert(input).unwrap_err();
+        for (input, exp) in tests {
+            assert_eq!(conv.convert(input).unwrap_err(), exp);
         }
     }
 
@@ -0,0 +1,152 @@
+use super::{
+    bits::{fits_width, mask},
+    fitting_width, InputError, IntermediateValue,
+};
+
+/// Operation applied to values after input conversion, in command line order
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BitOperation {
+    /// Rotate left by a number of bits, within the width
+    RotateLeft(u32),
+    /// Rotate right by a number of bits, within the width
+    RotateRight(u32),
+    /// Reverse the order of the bits, within the width
+    Reverse,
+    /// Reverse the order of the bytes, within the width
+    ByteSwap,
+    /// Read the lowest bits as a signed number
+    SignExtend(u32),
+    /// Read the lowest bits as an unsigned number
+    ZeroExtend(u32),
+    /// Keep the lowest bits, dropping the others
+    Truncate(u32),
+}
+
+impl BitOperation {
+    /// Apply operation to a value, on the selected width or the smallest that fits it
+    ///
+    /// Results of operations within a width are unsigned, so at 128 bits those with the top bit
+    /// set are out of the representable range
+    pub fn apply(
+        &self,
+        value: IntermediateValue,
+        width: Option<u32>,
+    ) -> Result<IntermediateValue, InputError> {
+        let width = match self {
+            Self::SignExtend(from) | Self::ZeroExtend(from) | Self::Truncate(from) => *from,
+            _ => width.unwrap_or_else(|| fitting_width(value)),
+        };
+        if !fits_width(value, width) && !matches!(self, Self::Truncate(_)) {
+            return Err(InputError::DoesNotFit(width));
+        }
+
+        let bits = value as u128 & mask(width);
+        let unused = u128::BITS - width;
+        let res = match self {
+            Self::RotateLeft(n) => rotate_left(bits, n % width, width),
+            Self::RotateRight(n) => rotate_left(bits, (width - n % width) % width, width),
+            Self::Reverse => bits.reverse_bits() >> unused,
+            Self::ByteSwap if width % 8 != 0 => return Err(InputError::NotWholeBytes(width)),
+            Self::ByteSwap => bits.swap_bytes() >> unused,
+            Self::SignExtend(_) => {
+                return Ok((bits << unused) as IntermediateValue >> unused);
+            }
+            Self::ZeroExtend(_) | Self::Truncate(_) => bits,
+        };
+        IntermediateValue::try_from(res).map_err(|_| InputError::Overflow)
+    }
+}
+
+/// Rotate the lowest bits of a width to the left
+fn rotate_left(bits: u128, n: u32, width: u32) -> u128 {
+    if n == 0 {
+        bits
+    } else {
+        (bits << n | bits >> (width - n)) & mask(width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_operation_apply_ok() {
+        let tests = [
+            (BitOperation::RotateLeft(4), 0x12, Some(8), 0x21),
+            (BitOperation::RotateLeft(9), 0x81, None, 0x03),
+            (BitOperation::RotateRight(1), 0x01, Some(16), 0x8000),
+            (BitOperation::RotateRight(0), 0x01, Some(128), 0x01),
+            (BitOperation::RotateRight(1), 0x02, Some(128), 0x01),
+            (BitOperation::RotateLeft(1), 1 << 125, Some(128), 1 << 126),
+            (BitOperation::RotateLeft(1), -1, Some(8), 0xFF),
+            (BitOperation::Reverse, 0x01, None, 0x80),
+            (BitOperation::Reverse, 0b0110, Some(4), 0b0110),
+            (BitOperation::ByteSwap, 0x1234, None, 0x3412),
+            (BitOperation::ByteSwap, 0x12, Some(32), 0x12000000),
+            (BitOperation::SignExtend(8), 0x80, None, -128),
+            (BitOperation::SignExtend(8), 0x7F, None, 127),
+            (BitOperation::SignExtend(4), -1, None, -1),
+            (BitOperation::SignExtend(128), -5, None, -5),
+            (BitOperation::ZeroExtend(8), -128, None, 0x80),
+            (BitOperation::ZeroExtend(16), 0xFF, None, 0xFF),
+            (BitOperation::Truncate(8), 0x1234, None, 0x34),
+            (BitOperation::Truncate(4), -1, None, 0xF),
+        ];
+        for (op, value, width, exp) in tests {
+            assert_eq!(op.apply(value, width).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn bit_operation_apply_err() {
+        let tests = [
+            (
+                BitOperation::SignExtend(8),
+                0x100,
+                None,
+                InputError::DoesNotFit(8),
+            ),
+            (
+                BitOperation::ZeroExtend(8),
+                -129,
+                None,
+                InputError::DoesNotFit(8),
+            ),
+            (
+                BitOperation::RotateLeft(1),
+                0x100,
+                Some(8),
+                InputError::DoesNotFit(8),
+            ),
+            (
+                BitOperation::ByteSwap,
+                0x12,
+                Some(12),
+                InputError::NotWholeBytes(12),
+            ),
+            (
+                BitOperation::RotateRight(1),
+                0x01,
+                Some(128),
+                InputError::Overflow,
+            ),
+            (BitOperation::Reverse, 0x01, Some(128), InputError::Overflow),
+            (
+                BitOperation::ByteSwap,
+                0x80,
+                Some(128),
+                InputError::Overflow,
+            ),
+            (
+                BitOperation::ZeroExtend(128),
+                -1,
+                None,
+                InputError::Overflow,
+            ),
+        ];
+        for (op, value, width, exp) in tests {
+            assert_eq!(op.apply(value, width), Err(exp));
+        }
+    }
+}
@@ -0,0 +1,544 @@
+mod svd;
+
+use nom::{
+    character::complete::{char, u32},
+    combinator::{all_consuming, opt},
+    sequence::{pair, preceded},
+    IResult,
+};
+use serde::{de, Deserialize};
+use std::{collections::HashMap, str::FromStr, sync::OnceLock};
+
+use super::{
+    bits::mask, AlreadyInUse, InputConverter, InputConverterType, InputError, IntermediateValue,
+    OutputConverter, OutputError,
+};
+
+pub use svd::SvdError;
+
+/// Register layouts in use, set once at startup
+static REGISTERS: OnceLock<Registers> = OnceLock::new();
+
+/// Layout of registers that are not declared
+static EMPTY_LAYOUT: RegisterLayout = RegisterLayout { fields: Vec::new() };
+
+/// Set the register layouts available to the output converters
+///
+/// Fails after the layouts have been used
+pub fn set_registers(registers: Registers) -> Result<(), AlreadyInUse> {
+    REGISTERS
+        .set(registers)
+        .map_err(|_| AlreadyInUse("register layouts"))
+}
+
+/// Get the register layouts in use
+pub fn registers() -> &'static Registers {
+    REGISTERS.get_or_init(Registers::default)
+}
+
+/// Register layouts declared in the config file, by name
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+pub struct Registers(HashMap<String, RegisterLayout>);
+
+impl Registers {
+    /// Find a register layout by name, ignoring case if there is no exact match
+    pub fn get(&self, name: &str) -> Option<(&str, &RegisterLayout)> {
+        self.0
+            .get_key_value(name)
+            .or_else(|| {
+                self.0
+                    .iter()
+                    .find(|(register, _)| register.eq_ignore_ascii_case(name))
+            })
+            .map(|(register, layout)| (register.as_str(), layout))
+    }
+
+    /// Get the names of all registers
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// Add the registers of another set, replacing those with the same name
+    pub fn merge(&mut self, other: Registers) {
+        self.0.extend(other.0);
+    }
+}
+
+impl FromIterator<(String, RegisterLayout)> for Registers {
+    fn from_iter<T: IntoIterator<Item = (String, RegisterLayout)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<const N: usize> From<[(&str, RegisterLayout); N]> for Registers {
+    fn from(value: [(&str, RegisterLayout); N]) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|(name, layout)| (name.to_string(), layout))
+                .collect(),
+        )
+    }
+}
+
+/// Fields of a register, from the highest bits
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+#[serde(try_from = "HashMap<String, FieldConfig>")]
+pub struct RegisterLayout {
+    pub fields: Vec<Field>,
+}
+
+/// Named range of bits of a register, with optional names of its values
+#[derive(Debug, PartialEq, Clone)]
+pub struct Field {
+    pub name: String,
+    pub bits: BitRange,
+    pub values: HashMap<u128, String>,
+}
+
+/// Field as written in the config file. Ex: [15, 4] or { bits = "17:16", values = { 0 = "8N1" } }
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FieldConfig {
+    Bits(BitRange),
+    Detailed {
+        bits: BitRange,
+        #[serde(default)]
+        values: HashMap<String, String>,
+    },
+}
+
+impl TryFrom<HashMap<String, FieldConfig>> for RegisterLayout {
+    type Error = String;
+
+    fn try_from(value: HashMap<String, FieldConfig>) -> Result<Self, Self::Error> {
+        let mut fields = value
+            .into_iter()
+            .map(|(name, field)| {
+                let (bits, values) = match field {
+                    FieldConfig::Bits(bits) => (bits, HashMap::new()),
+                    FieldConfig::Detailed { bits, values } => (bits, values),
+                };
+                let values = values
+                    .into_iter()
+                    .map(|(value, meaning)| {
+                        let inconv = InputConverterType::from_notation(&value)
+                            .unwrap_or(InputConverterType::DEC);
+                        match inconv.get_converter().convert(&value).map(u128::try_from) {
+                            Ok(Ok(value)) if value <= mask(bits.width()) => Ok((value, meaning)),
+                            _ => Err(format!("Invalid value of field {}: {}", name, value)),
+                        }
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(Field { name, bits, values })
+            })
+            .collect::<Result<Vec<Field>, String>>()?;
+
+        fields.sort_by(|a, b| b.bits.high.cmp(&a.bits.high).then(a.name.cmp(&b.name)));
+
+        // Sorted from the highest bits, a field overlapping others overlaps the next one
+        if let Some(pair) = fields
+            .windows(2)
+            .find(|pair| pair[1].bits.high >= pair[0].bits.low)
+        {
+            return Err(format!(
+                "Overlapping fields {} and {}",
+                pair[0].name, pair[1].name
+            ));
+        }
+        Ok(Self { fields })
+    }
+}
+
+/// Range of bits, both ends included
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BitRange {
+    pub high: u32,
+    pub low: u32,
+}
+
+impl BitRange {
+    /// Build range, if its ends are in order and within the intermediate type
+    pub fn new(high: u32, low: u32) -> Option<Self> {
+        (low <= high && high < IntermediateValue::BITS).then_some(Self { high, low })
+    }
+
+    /// Number of bits in the range
+    pub fn width(&self) -> u32 {
+        self.high - self.low + 1
+    }
+
+    /// Bits of the range set, in place
+    pub fn mask(&self) -> u128 {
+        mask(self.width()) << self.low
+    }
+
+    /// Take the bits in the range from a value, shifted to the lowest bits
+    pub fn extract(&self, value: u128) -> u128 {
+        value >> self.low & mask(self.width())
+    }
+
+    /// Replace the bits in the range of a value with the lowest bits of another
+    pub fn insert(&self, value: u128, bits: u128) -> u128 {
+        value & !self.mask() | bits << self.low & self.mask()
+    }
+}
+
+impl FromStr for BitRange {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (_, (high, low)) = parse_bit_range(s).map_err(|_| ())?;
+        Self::new(high, low.unwrap_or(high)).ok_or(())
+    }
+}
+
+/// Bit range as written in the config file
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BitRangeConfig {
+    Bit(u32),
+    Bits(Vec<u32>),
+    Text(String),
+}
+
+impl<'de> Deserialize<'de> for BitRange {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let range = match BitRangeConfig::deserialize(deserializer)? {
+            BitRangeConfig::Bit(bit) => Self::new(bit, bit),
+            BitRangeConfig::Bits(bits) => match bits[..] {
+                [bit] => Self::new(bit, bit),
+                [high, low] => Self::new(high, low),
+                _ => None,
+            },
+            BitRangeConfig::Text(s) => s.parse().ok(),
+        };
+        range.ok_or_else(|| {
+            de::Error::custom("Invalid bit range, expected [high, low], [bit] or \"high:low\"")
+        })
+    }
+}
+
+impl RegisterLayout {
+    /// Get layout of a register declared in the config file
+    fn named(name: &str) -> &'static Self {
+        registers()
+            .get(name)
+            .map_or(&EMPTY_LAYOUT, |(_, layout)| layout)
+    }
+
+    /// Find a field by name, ignoring case
+    fn field(&self, name: &str) -> Option<&Field> {
+        self.fields
+            .iter()
+            .find(|field| field.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl Field {
+    /// Read the value of a field, as a number or as the name of a value
+    fn parse_value(&self, value: &str) -> Option<u128> {
+        let named = self
+            .values
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(value))
+            .map(|(raw, _)| *raw);
+        let number = || {
+            InputConverterType::from_notation(value)
+                .unwrap_or(InputConverterType::DEC)
+                .get_converter()
+                .convert(value)
+                .ok()
+                .and_then(|raw| u128::try_from(raw).ok())
+        };
+        named
+            .or_else(number)
+            .filter(|raw| *raw <= mask(self.bits.width()))
+    }
+}
+
+/// Input converter that encodes assignments to the fields of a register
+///
+/// Ex: EN=1,BAUD=58,MODE=7E1. Fields that are not assigned are 0
+pub struct RegisterInputConverter<'a> {
+    pub layout: &'a RegisterLayout,
+}
+
+impl RegisterInputConverter<'static> {
+    /// Get converter for a register declared in the config file
+    pub fn named(name: &str) -> Self {
+        Self {
+            layout: RegisterLayout::named(name),
+        }
+    }
+}
+
+impl InputConverter for RegisterInputConverter<'_> {
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        let assignments: Vec<&str> = input
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|assignment| !assignment.is_empty())
+            .collect();
+        if assignments.is_empty() {
+            return Err(InputError::Empty);
+        }
+
+        let mut value = 0;
+        for assignment in assignments {
+            let (name, raw) = assignment
+                .split_once('=')
+                .ok_or_else(|| InputError::MissingFieldValue(assignment.to_string()))?;
+            let field = self
+                .layout
+                .field(name)
+                .ok_or_else(|| InputError::UnknownField(name.to_string()))?;
+            let raw = field.parse_value(raw).ok_or_else(|| {
+                InputError::InvalidFieldValue(field.name.clone(), raw.to_string())
+            })?;
+            // A field assigned again keeps the last value
+            value = field.bits.insert(value, raw);
+        }
+
+        IntermediateValue::try_from(value).map_err(|_| InputError::Overflow)
+    }
+}
+
+/// Output converter that decodes the fields of a register
+pub struct RegisterOutputConverter<'a> {
+    pub layout: &'a RegisterLayout,
+}
+
+impl RegisterOutputConverter<'static> {
+    /// Get converter for a register declared in the config file
+    pub fn named(name: &str) -> Self {
+        Self {
+            layout: RegisterLayout::named(name),
+        }
+    }
+}
+
+impl OutputConverter for RegisterOutputConverter<'_> {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        // Don't decode negative values
+        if input < 0 {
+            return Err(OutputError::Negative);
+        }
+        if self.layout.fields.is_empty() {
+            return Err(OutputError::NoFields);
+        }
+
+        let fields: Vec<String> = self
+            .layout
+            .fields
+            .iter()
+            .map(|field| {
+                let raw = field.bits.extract(input as u128);
+                match field.values.get(&raw) {
+                    Some(meaning) => format!("{}={} ({})", field.name, raw, meaning),
+                    None => format!("{}={}", field.name, raw),
+                }
+            })
+            .collect();
+        Ok(fields.join("; "))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        if let Err(err) = self.convert(input) {
+            return Some(vec![err.to_string()]);
+        }
+
+        let steps = self
+            .layout
+            .fields
+            .iter()
+            .map(|field| {
+                let raw = field.bits.extract(input as u128);
+                let bits = if field.bits.width() == 1 {
+                    format!("bit {}", field.bits.low)
+                } else {
+                    format!("bits {}:{}", field.bits.high, field.bits.low)
+                };
+                let step = format!(
+                    "{} is {}: ({} >> {}) & 0x{:X} = {}",
+                    field.name,
+                    bits,
+                    input,
+                    field.bits.low,
+                    mask(field.bits.width()),
+                    raw
+                );
+                match field.values.get(&raw) {
+                    Some(meaning) => format!("{}, which means {}", step, meaning),
+                    None => step,
+                }
+            })
+            .collect();
+        Some(steps)
+    }
+}
+
+/// Parse bit range. Ex: 15:4, 7
+fn parse_bit_range(input: &str) -> IResult<&str, (u32, Option<u32>)> {
+    all_consuming(pair(u32, opt(preceded(char(':'), u32))))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uart_cr() -> RegisterLayout {
+        toml::from_str(
+            r#"
+            EN = [0]
+            BAUD = "15:4"
+            MODE = { bits = [17, 16], values = { 0 = "8N1", 0x1 = "7E1" } }
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn bit_range_from_str_ok() {
+        let tests = [
+            ("15:4", BitRange { high: 15, low: 4 }),
+            ("7", BitRange { high: 7, low: 7 }),
+            ("127:0", BitRange { high: 127, low: 0 }),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(input.parse::<BitRange>().unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn bit_range_from_str_err() {
+        let tests = ["", "4:15", "128", "15:", ":4", "15-4", "a"];
+        for input in tests {
+            input.parse::<BitRange>().unwrap_err();
+        }
+    }
+
+    #[test]
+    fn register_layout_deserialize_ok() {
+        let layout = uart_cr();
+        let names: Vec<&str> = layout
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["MODE", "BAUD", "EN"]);
+        assert_eq!(layout.fields[0].bits, BitRange { high: 17, low: 16 });
+        assert_eq!(layout.fields[0].values.get(&1).unwrap(), "7E1");
+        assert_eq!(layout.fields[2].bits, BitRange { high: 0, low: 0 });
+    }
+
+    #[test]
+    fn register_layout_deserialize_err() {
+        let tests = [
+            "EN = [0, 1, 2]",
+            "EN = [128]",
+            "EN = \"4:15\"",
+            "EN = true",
+            "MODE = { bits = [1, 0], values = { 4 = \"x\" } }",
+            "MODE = { bits = [1, 0], values = { a = \"x\" } }",
+            "MODE = { bits = [1, 0], values = { 0b100 = \"x\" } }",
+            "MODE = { bits = [1, 0], values = { -1 = \"x\" } }",
+            "EN = [0]\nMODE = [1, 0]",
+            "BAUD = [15, 4]\nMODE = [7, 6]",
+        ];
+        for input in tests {
+            toml::from_str::<RegisterLayout>(input).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn register_outconv_ok() {
+        let layout = uart_cr();
+        let converter = RegisterOutputConverter { layout: &layout };
+        let tests = [
+            (0x0003A1, "MODE=0 (8N1); BAUD=58; EN=1"),
+            (0x0103A0, "MODE=1 (7E1); BAUD=58; EN=0"),
+            (0x030000, "MODE=3; BAUD=0; EN=0"),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(converter.convert(input).unwrap(), exp);
+        }
+        assert_eq!(converter.convert(-1), Err(OutputError::Negative));
+
+        let empty = RegisterOutputConverter {
+            layout: &EMPTY_LAYOUT,
+        };
+        assert_eq!(empty.convert(0), Err(OutputError::NoFields));
+        assert_eq!(empty.explain(0).unwrap(), vec!["register has no fields"]);
+    }
+
+    #[test]
+    fn register_outconv_explain() {
+        let layout = uart_cr();
+        let converter = RegisterOutputConverter { layout: &layout };
+        assert_eq!(
+            converter.explain(0x10001).unwrap(),
+            vec![
+                "MODE is bits 17:16: (65537 >> 16) & 0x3 = 1, which means 7E1",
+                "BAUD is bits 15:4: (65537 >> 4) & 0xFFF = 0",
+                "EN is bit 0: (65537 >> 0) & 0x1 = 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn register_inconv_ok() {
+        let layout = uart_cr();
+        let converter = RegisterInputConverter { layout: &layout };
+        let tests = [
+            ("EN=1,BAUD=58,MODE=8N1", 0x0003A1),
+            ("mode=7e1 baud=0x3A", 0x0103A0),
+            ("MODE=3", 0x030000),
+            ("EN=1,EN=0", 0x000000),
+            ("BAUD=0xFFF,BAUD=1", 0x000010),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(converter.convert(input).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn register_inconv_err() {
+        let layout = uart_cr();
+        let converter = RegisterInputConverter { layout: &layout };
+        let tests = [
+            ("", InputError::Empty),
+            (" , ", InputError::Empty),
+            ("EN", InputError::MissingFieldValue("EN".to_string())),
+            (
+                "EN=1,PARITY=1",
+                InputError::UnknownField("PARITY".to_string()),
+            ),
+            (
+                "MODE=4",
+                InputError::InvalidFieldValue("MODE".to_string(), "4".to_string()),
+            ),
+            (
+                "MODE=9N1",
+                InputError::InvalidFieldValue("MODE".to_string(), "9N1".to_string()),
+            ),
+            (
+                "EN=-1",
+                InputError::InvalidFieldValue("EN".to_string(), "-1".to_string()),
+            ),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(converter.convert(input), Err(exp));
+        }
+    }
+
+    #[test]
+    fn registers_get_ok() {
+        let registers = Registers::from([("UART_CR", uart_cr())]);
+        assert_eq!(registers.get("UART_CR").unwrap().0, "UART_CR");
+        assert_eq!(registers.get("uart_cr").unwrap().0, "UART_CR");
+        assert!(registers.get("UART").is_none());
+    }
+}
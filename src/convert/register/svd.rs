@@ -0,0 +1,423 @@
+use roxmltree::{Document, Node};
+use std::{collections::HashMap, fs, io, path::Path};
+
+use super::{BitRange, Field, RegisterLayout, Registers};
+
+/// Largest number of elements repeated with <dim>
+const MAX_DIM: usize = 1 << 16;
+
+/// Error encountered while reading a CMSIS-SVD file
+#[derive(Debug)]
+pub enum SvdError {
+    Read(io::Error),
+    Xml(roxmltree::Error),
+    /// Element that cannot be understood, with the reason
+    Invalid(String),
+}
+
+impl Registers {
+    /// Read the registers of all peripherals from a CMSIS-SVD file
+    ///
+    /// Registers are named after their peripheral. Ex: UART0.CR
+    pub fn from_svd_file(path: &Path) -> Result<Self, SvdError> {
+        let contents = fs::read_to_string(path).map_err(SvdError::Read)?;
+        Self::from_svd(&contents)
+    }
+
+    /// Read the registers of all peripherals from the contents of a CMSIS-SVD file
+    pub fn from_svd(contents: &str) -> Result<Self, SvdError> {
+        let document = Document::parse(contents).map_err(SvdError::Xml)?;
+        let peripherals: Vec<Node> = document
+            .descendants()
+            .filter(|node| node.has_tag_name("peripheral"))
+            .collect();
+
+        let mut registers = Vec::new();
+        for peripheral in &peripherals {
+            let name = child_text(*peripheral, "name")
+                .ok_or_else(|| SvdError::Invalid("peripheral without a name".to_string()))?;
+
+            // Derived peripherals may share the registers of another one
+            let source = derived_source(*peripheral, &peripherals, "registers")?;
+
+            if let Some(block) = child(source, "registers") {
+                read_registers(block, name, &mut registers)?;
+            }
+        }
+
+        Ok(registers.into_iter().collect())
+    }
+}
+
+/// Find the element holding the contents of another one, following derivedFrom chains
+///
+/// Elements are derived from one of the candidates with the same tag, unless they have their own
+/// contents. Ex: a peripheral without <registers>, a register without <fields>
+fn derived_source<'a, 'input>(
+    node: Node<'a, 'input>,
+    candidates: &[Node<'a, 'input>],
+    contents: &str,
+) -> Result<Node<'a, 'input>, SvdError> {
+    let kind = node.tag_name().name();
+    let mut source = node;
+    // A chain longer than the number of candidates loops
+    for _ in 0..=candidates.len() {
+        let base = match source.attribute("derivedFrom") {
+            Some(base) if child(source, contents).is_none() => base,
+            _ => return Ok(source),
+        };
+        source = *candidates
+            .iter()
+            .find(|other| other.has_tag_name(kind) && child_text(**other, "name") == Some(base))
+            .ok_or_else(|| {
+                SvdError::Invalid(format!(
+                    "{} {} derived from unknown {} {}",
+                    kind,
+                    child_text(source, "name").unwrap_or_default(),
+                    kind,
+                    base
+                ))
+            })?;
+    }
+    Err(SvdError::Invalid(format!(
+        "{} {} derived from itself",
+        kind,
+        child_text(node, "name").unwrap_or_default()
+    )))
+}
+
+/// Read the registers and clusters of a block, prefixing their names
+fn read_registers(
+    block: Node,
+    prefix: &str,
+    registers: &mut Vec<(String, RegisterLayout)>,
+) -> Result<(), SvdError> {
+    let nodes: Vec<Node> = block
+        .children()
+        .filter(|node| node.has_tag_name("register") || node.has_tag_name("cluster"))
+        .collect();
+    for node in &nodes {
+        let name = child_text(*node, "name")
+            .ok_or_else(|| SvdError::Invalid(format!("register without a name in {}", prefix)))?;
+
+        // Derived registers and clusters may share the fields or registers of a sibling
+        let source = if node.has_tag_name("cluster") {
+            derived_source(*node, &nodes, "register")?
+        } else {
+            derived_source(*node, &nodes, "fields")?
+        };
+
+        for name in dim_names(*node, name)? {
+            let name = format!("{}.{}", prefix, name);
+            if node.has_tag_name("cluster") {
+                read_registers(source, &name, registers)?;
+            } else {
+                let layout = read_fields(source, &name)?;
+                registers.push((name, layout));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Read the fields of a register
+fn read_fields(register: Node, name: &str) -> Result<RegisterLayout, SvdError> {
+    let Some(fields) = child(register, "fields") else {
+        return Ok(RegisterLayout::default());
+    };
+
+    let mut layout = RegisterLayout {
+        fields: fields
+            .children()
+            .filter(|node| node.has_tag_name("field"))
+            .map(|field| {
+                let field_name = child_text(field, "name").ok_or_else(|| {
+                    SvdError::Invalid(format!("field without a name in {}", name))
+                })?;
+                let bits = bit_range(field).ok_or_else(|| {
+                    SvdError::Invalid(format!("invalid bit range of {}.{}", name, field_name))
+                })?;
+                Ok(Field {
+                    name: field_name.to_string(),
+                    bits,
+                    values: enumerated_values(field),
+                })
+            })
+            .collect::<Result<_, _>>()?,
+    };
+
+    layout
+        .fields
+        .sort_by_key(|field| std::cmp::Reverse(field.bits.high));
+    Ok(layout)
+}
+
+/// Read the bit range of a field, in any of the three forms allowed
+///
+/// Ex: <bitRange>[15:4]</bitRange>, <lsb>4</lsb><msb>15</msb>, <bitOffset>4</bitOffset><bitWidth>12</bitWidth>
+fn bit_range(field: Node) -> Option<BitRange> {
+    let number = |name| child_text(field, name).and_then(parse_number);
+
+    if let Some(range) = child_text(field, "bitRange") {
+        return range
+            .trim()
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .parse()
+            .ok();
+    }
+    if let (Some(lsb), Some(msb)) = (number("lsb"), number("msb")) {
+        return BitRange::new(msb.try_into().ok()?, lsb.try_into().ok()?);
+    }
+
+    let offset: u32 = number("bitOffset")?.try_into().ok()?;
+    let width: u32 = number("bitWidth").unwrap_or(1).try_into().ok()?;
+    BitRange::new(offset.checked_add(width)?.checked_sub(1)?, offset)
+}
+
+/// Read the names of the values of a field, skipping those without a single value
+fn enumerated_values(field: Node) -> HashMap<u128, String> {
+    field
+        .descendants()
+        .filter(|node| node.has_tag_name("enumeratedValue"))
+        .filter_map(|node| {
+            let value = child_text(node, "value").and_then(parse_number)?;
+            Some((value, child_text(node, "name")?.to_string()))
+        })
+        .collect()
+}
+
+/// Expand the names of an element repeated with <dim>. Ex: CH%s, CH[%s]
+///
+/// Fails if the <dimIndex> list doesn't have <dim> indexes, or if there are too many
+fn dim_names(node: Node, name: &str) -> Result<Vec<String>, SvdError> {
+    let Some(dim) = child_text(node, "dim").and_then(parse_number) else {
+        return Ok(vec![name.to_string()]);
+    };
+    let invalid = || SvdError::Invalid(format!("invalid dimension of {}", name));
+    let dim = usize::try_from(dim)
+        .ok()
+        .filter(|dim| *dim <= MAX_DIM)
+        .ok_or_else(invalid)?;
+
+    let indexes: Vec<String> = match child_text(node, "dimIndex") {
+        Some(list) => dim_indexes(list, dim).ok_or_else(invalid)?,
+        None => (0..dim).map(|index| index.to_string()).collect(),
+    };
+    Ok(indexes
+        .iter()
+        .map(|index| name.replace("[%s]", index).replace("%s", index))
+        .collect())
+}
+
+/// Expand a list of <dim> indexes, either a range or comma separated. Ex: 0-3, A-C, RX,TX
+fn dim_indexes(list: &str, dim: usize) -> Option<Vec<String>> {
+    // Ranges are checked before being expanded
+    if let Some((first, last)) = list.split_once('-') {
+        let (first, last) = (first.trim(), last.trim());
+        if let (Ok(first), Ok(last)) = (first.parse::<u128>(), last.parse::<u128>()) {
+            return (last.checked_sub(first)?.checked_add(1)? == dim as u128)
+                .then(|| (first..=last).map(|index| index.to_string()).collect());
+        }
+        let letter = |text: &str| {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_uppercase() => Some(c),
+                _ => None,
+            }
+        };
+        if let (Some(first), Some(last)) = (letter(first), letter(last)) {
+            return (first <= last && (last as u32 - first as u32 + 1) as usize == dim)
+                .then(|| (first..=last).map(|index| index.to_string()).collect());
+        }
+    }
+    let indexes: Vec<String> = list
+        .split(',')
+        .map(|index| index.trim().to_string())
+        .collect();
+    (indexes.len() == dim).then_some(indexes)
+}
+
+/// Parse a number as written in SVD files. Ex: 42, 0x2A, #101010
+fn parse_number(text: &str) -> Option<u128> {
+    let text = text.trim();
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        u128::from_str_radix(hex, 16).ok()
+    } else if let Some(bin) = text.strip_prefix('#').or_else(|| text.strip_prefix("0b")) {
+        u128::from_str_radix(bin, 2).ok()
+    } else {
+        text.parse().ok()
+    }
+}
+
+/// Get the first child element with a given tag name
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+/// Get the text of the first child element with a given tag name
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name)
+        .and_then(|child| child.text())
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVD: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<device schemaVersion="1.3">
+  <name>TEST</name>
+  <peripherals>
+    <peripheral>
+      <name>UART0</name>
+      <baseAddress>0x40000000</baseAddress>
+      <registers>
+        <register>
+          <name>CR</name>
+          <addressOffset>0x0</addressOffset>
+          <fields>
+            <field>
+              <name>EN</name>
+              <bitOffset>0</bitOffset>
+              <bitWidth>1</bitWidth>
+            </field>
+            <field>
+              <name>BAUD</name>
+              <bitRange>[15:4]</bitRange>
+            </field>
+            <field>
+              <name>MODE</name>
+              <lsb>16</lsb>
+              <msb>17</msb>
+              <enumeratedValues>
+                <enumeratedValue><name>8N1</name><value>0</value></enumeratedValue>
+                <enumeratedValue><name>7E1</name><value>0x1</value></enumeratedValue>
+                <enumeratedValue><name>OTHER</name><isDefault>true</isDefault></enumeratedValue>
+              </enumeratedValues>
+            </field>
+          </fields>
+        </register>
+        <register derivedFrom="CR">
+          <name>CR2</name>
+          <addressOffset>0x4</addressOffset>
+        </register>
+        <cluster>
+          <name>CH[%s]</name>
+          <dim>2</dim>
+          <register>
+            <name>CFG</name>
+          </register>
+        </cluster>
+      </registers>
+    </peripheral>
+    <peripheral derivedFrom="UART0">
+      <name>UART1</name>
+      <baseAddress>0x40001000</baseAddress>
+    </peripheral>
+    <peripheral derivedFrom="UART1">
+      <name>UART2</name>
+      <baseAddress>0x40002000</baseAddress>
+    </peripheral>
+  </peripherals>
+</device>
+"#;
+
+    #[test]
+    fn registers_from_svd_ok() {
+        let registers = Registers::from_svd(SVD).unwrap();
+
+        let mut names: Vec<&str> = registers.names().collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "UART0.CH0.CFG",
+                "UART0.CH1.CFG",
+                "UART0.CR",
+                "UART0.CR2",
+                "UART1.CH0.CFG",
+                "UART1.CH1.CFG",
+                "UART1.CR",
+                "UART1.CR2",
+                "UART2.CH0.CFG",
+                "UART2.CH1.CFG",
+                "UART2.CR",
+                "UART2.CR2",
+            ]
+        );
+        assert_eq!(
+            registers.get("uart2.cr2").unwrap().1,
+            registers.get("uart0.cr").unwrap().1
+        );
+
+        let (_, layout) = registers.get("uart2.cr").unwrap();
+        let fields: Vec<(&str, BitRange)> = layout
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.bits))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("MODE", BitRange { high: 17, low: 16 }),
+                ("BAUD", BitRange { high: 15, low: 4 }),
+                ("EN", BitRange { high: 0, low: 0 }),
+            ]
+        );
+        assert_eq!(
+            layout.fields[0].values,
+            HashMap::from([(0, "8N1".to_string()), (1, "7E1".to_string())])
+        );
+    }
+
+    #[test]
+    fn registers_from_svd_err() {
+        let tests = [
+            "<device><peripherals>",
+            "<device><peripherals><peripheral><registers/></peripheral></peripherals></device>",
+            "<device><peripherals><peripheral derivedFrom=\"X\"><name>A</name></peripheral></peripherals></device>",
+            "<device><peripherals><peripheral derivedFrom=\"B\"><name>A</name></peripheral><peripheral derivedFrom=\"A\"><name>B</name></peripheral></peripherals></device>",
+            "<device><peripherals><peripheral><name>A</name><registers><register derivedFrom=\"X\"><name>R</name></register></registers></peripheral></peripherals></device>",
+            "<device><peripherals><peripheral><name>A</name><registers><register><name>R%s</name><dim>4</dim><dimIndex>0-4294967295</dimIndex></register></registers></peripheral></peripherals></device>",
+            "<device><peripherals><peripheral><name>A</name><registers><register><name>R</name><fields><field><name>F</name><bitOffset>4294967295</bitOffset><bitWidth>2</bitWidth></field></fields></register></registers></peripheral></peripherals></device>",
+            "<device><peripherals><peripheral><name>A</name><registers><register><name>R</name><fields><field><name>F</name><bitRange>[4:15]</bitRange></field></fields></register></registers></peripheral></peripherals></device>",
+        ];
+        for input in tests {
+            Registers::from_svd(input).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn dim_indexes_ok() {
+        let tests = [
+            ("0-3", 4, Some(vec!["0", "1", "2", "3"])),
+            ("A-C", 3, Some(vec!["A", "B", "C"])),
+            ("RX, TX", 2, Some(vec!["RX", "TX"])),
+            ("7", 1, Some(vec!["7"])),
+            ("0-4294967295", 4, None),
+            ("3-0", 4, None),
+            ("C-A", 3, None),
+            ("RX,TX", 3, None),
+        ];
+        for (input, dim, exp) in tests {
+            let exp = exp.map(|indexes| indexes.into_iter().map(str::to_string).collect());
+            assert_eq!(dim_indexes(input, dim), exp);
+        }
+    }
+
+    #[test]
+    fn parse_number_ok() {
+        let tests = [
+            ("42", Some(42)),
+            (" 0x2A ", Some(42)),
+            ("#101010", Some(42)),
+            ("#1x1", None),
+            ("", None),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(parse_number(input), exp);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use super::{
+    bits::fits_width, register::BitRange, InputConverter, InputConverterType, InputError,
+    IntermediateValue,
+};
+
+/// Bit range selected after an input value, to extract or replace its bits
+///
+/// Ex: 0xDEADBEEF[15:8] or 0xDEADBEEF[15:8]=0x42
+#[derive(Debug, PartialEq)]
+pub struct BitSlice<'a> {
+    /// Input value before the bit range
+    pub value: &'a str,
+    /// Bit range, as written between the brackets
+    pub range: &'a str,
+    /// Value written to the bit range, if replacing it
+    pub replacement: Option<&'a str>,
+}
+
+impl<'a> BitSlice<'a> {
+    /// Split a bit range from the end of an input value, if there is one
+    pub fn split(input: &'a str) -> Option<Self> {
+        let (rest, replacement) = match input.rsplit_once("]=") {
+            Some((rest, replacement)) => (rest, Some(replacement)),
+            None => (input.strip_suffix(']')?, None),
+        };
+        let (value, range) = rest.rsplit_once('[')?;
+
+        Some(Self {
+            value,
+            range,
+            replacement,
+        })
+    }
+
+    /// Parse the bit range
+    pub fn bit_range(&self) -> Result<BitRange, InputError> {
+        self.range
+            .parse()
+            .map_err(|_| InputError::InvalidBitRange(self.range.to_string()))
+    }
+
+    /// Parse the value written to the bit range, in any base with a prefix or suffix
+    pub fn replacement_value(&self, range: BitRange) -> Option<Result<u128, InputError>> {
+        let replacement = self.replacement?;
+        let inconv =
+            InputConverterType::from_notation(replacement).unwrap_or(InputConverterType::DEC);
+        let bits = inconv
+            .get_converter()
+            .convert(replacement)
+            .ok()
+            .filter(|bits| *bits >= 0 && fits_width(*bits, range.width()))
+            .ok_or_else(|| InputError::InvalidRangeValue(replacement.to_string(), range.width()));
+        Some(bits.map(|bits| bits as u128))
+    }
+
+    /// Extract or replace the bits of the range in a value read before it
+    pub fn apply(&self, value: IntermediateValue) -> Result<IntermediateValue, InputError> {
+        let range = self.bit_range()?;
+        match self.replacement_value(range) {
+            Some(bits) => Ok(range.insert(value as u128, bits?) as IntermediateValue),
+            None => Ok(range.extract(value as u128) as IntermediateValue),
+        }
+    }
+}
+
+/// Read an input value, applying the bit range selected after it if any
+pub fn read_input(
+    inconv: &InputConverterType,
+    input: &str,
+) -> Result<IntermediateValue, InputError> {
+    match BitSlice::split(input) {
+        Some(slice) => slice.apply(inconv.get_converter().convert(slice.value)?),
+        None => inconv.get_converter().convert(input),
+    }
+}
+
+/// Input converter that builds a mask from a bit range. Ex: 15:8 -> 0xFF00
+pub struct MaskInputConverter;
+
+impl InputConverter for MaskInputConverter {
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        if input.is_empty() {
+            return Err(InputError::Empty);
+        }
+
+        let range: BitRange = input
+            .parse()
+            .map_err(|_| InputError::InvalidBitRange(input.to_string()))?;
+        Ok(range.mask() as IntermediateValue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_slice_split_ok() {
+        let tests = [
+            (
+                "0xDEADBEEF[15:8]",
+                Some(BitSlice {
+                    value: "0xDEADBEEF",
+                    range: "15:8",
+                    replacement: None,
+                }),
+            ),
+            (
+                "0xDEADBEEF[15:8]=0x42",
+                Some(BitSlice {
+                    value: "0xDEADBEEF",
+                    range: "15:8",
+                    replacement: Some("0x42"),
+                }),
+            ),
+            (
+                "12[]",
+                Some(BitSlice {
+                    value: "12",
+                    range: "",
+                    replacement: None,
+                }),
+            ),
+            ("0xDEADBEEF", None),
+            ("]", None),
+            ("15:8]=1", None),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(BitSlice::split(input), exp);
+        }
+    }
+
+    #[test]
+    fn read_input_ok() {
+        let tests = [
+            (InputConverterType::HEX, "0xDEADBEEF[15:8]", 0xBE),
+            (InputConverterType::HEX, "0xDEADBEEF[31]", 1),
+            (InputConverterType::HEX, "0xDEADBEEF[15:8]=0x42", 0xDEAD42EF),
+            (InputConverterType::DEC, "255[7:4]=0b0", 0xF),
+            (InputConverterType::DEC, "-1[7:0]", 0xFF),
+            (InputConverterType::DEC, "-1[3:0]=0", -16),
+        ];
+        for (inconv, input, exp) in tests {
+            assert_eq!(read_input(&inconv, input).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn read_input_err() {
+        let tests = [
+            (
+                "0xDEADBEEF[8:15]",
+                InputError::InvalidBitRange("8:15".to_string()),
+            ),
+            (
+                "0xDEADBEEF[15:8]=0x100",
+                InputError::InvalidRangeValue("0x100".to_string(), 8),
+            ),
+            (
+                "0xDEADBEEF[15:8]=-1",
+                InputError::InvalidRangeValue("-1".to_string(), 8),
+            ),
+            ("0xDEADBEEG[15:8]", InputError::InvalidCharacter(9, 'G')),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(read_input(&InputConverterType::HEX, input), Err(exp));
+        }
+    }
+
+    #[test]
+    fn mask_inconv_ok() {
+        let tests = [("15:8", 0xFF00), ("0", 1), ("127:0", -1)];
+        for (input, exp) in tests {
+            assert_eq!(MaskInputConverter.convert(input).unwrap(), exp);
+        }
+        assert_eq!(
+            MaskInputConverter.convert("8:15"),
+            Err(InputError::InvalidBitRange("8:15".to_string()))
+        );
+    }
+}
@@ -0,0 +1,407 @@
+use serde::Deserialize;
+use std::{collections::HashMap, sync::OnceLock};
+
+use super::{
+    AlreadyInUse, InputConverter, InputConverterType, InputError, IntermediateValue,
+    OutputConverter, OutputError,
+};
+
+/// Constants in use, set once at startup
+static CONSTANTS: OnceLock<Constants> = OnceLock::new();
+
+/// Enumerations in use, set once at startup
+static ENUMS: OnceLock<Enums> = OnceLock::new();
+
+/// Enumeration used for names that are not declared
+static EMPTY_ENUM: OnceLock<Enumeration> = OnceLock::new();
+
+/// Set the constants available to the symbol input converter
+///
+/// Fails after the constants have been used
+pub fn set_constants(constants: Constants) -> Result<(), AlreadyInUse> {
+    CONSTANTS
+        .set(constants)
+        .map_err(|_| AlreadyInUse("constants"))
+}
+
+/// Get the constants in use
+pub fn constants() -> &'static Constants {
+    CONSTANTS.get_or_init(Constants::default)
+}
+
+/// Set the enumerations available to the converters
+///
+/// Fails after the enumerations have been used
+pub fn set_enums(enums: Enums) -> Result<(), AlreadyInUse> {
+    ENUMS.set(enums).map_err(|_| AlreadyInUse("enumerations"))
+}
+
+/// Get the enumerations in use
+pub fn enums() -> &'static Enums {
+    ENUMS.get_or_init(Enums::default)
+}
+
+/// Check if any constant or enumeration is declared, without setting them
+pub fn has_symbols() -> bool {
+    CONSTANTS
+        .get()
+        .is_some_and(|constants| !constants.0.is_empty())
+        || ENUMS.get().is_some_and(|enums| !enums.0.is_empty())
+}
+
+/// Named values declared in the config file
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+#[serde(try_from = "HashMap<String, ConstantConfig>")]
+pub struct Constants(HashMap<String, IntermediateValue>);
+
+/// Constant as written in the config file. Ex: 4096 or "0x1000"
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ConstantConfig {
+    Number(i64),
+    Text(String),
+}
+
+impl TryFrom<HashMap<String, ConstantConfig>> for Constants {
+    type Error = String;
+
+    fn try_from(value: HashMap<String, ConstantConfig>) -> Result<Self, Self::Error> {
+        // Names are looked up ignoring case, so they must differ by more than case
+        let mut names: Vec<&String> = value.keys().collect();
+        names.sort_by_key(|name| (name.to_ascii_uppercase(), *name));
+        if let Some(pair) = names
+            .windows(2)
+            .find(|pair| pair[0].eq_ignore_ascii_case(pair[1]))
+        {
+            return Err(format!(
+                "Constants {} and {} differ only in case",
+                pair[0], pair[1]
+            ));
+        }
+
+        value
+            .into_iter()
+            .map(|(name, constant)| match constant {
+                ConstantConfig::Number(value) => Ok((name, value.into())),
+                ConstantConfig::Text(value) => {
+                    let inconv = InputConverterType::from_notation(&value)
+                        .unwrap_or(InputConverterType::DEC);
+                    match inconv.get_converter().convert(&value) {
+                        Ok(value) => Ok((name, value)),
+                        Err(_) => Err(format!("Invalid value of {}: {}", name, value)),
+                    }
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl Constants {
+    /// Find the value of a constant, ignoring case if there is no exact match
+    pub fn get(&self, name: &str) -> Option<IntermediateValue> {
+        self.0
+            .get(name)
+            .or_else(|| {
+                self.0
+                    .iter()
+                    .find(|(constant, _)| constant.eq_ignore_ascii_case(name))
+                    .map(|(_, value)| value)
+            })
+            .copied()
+    }
+}
+
+impl<const N: usize> From<[(&str, IntermediateValue); N]> for Constants {
+    fn from(value: [(&str, IntermediateValue); N]) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        )
+    }
+}
+
+/// Enumerations declared in the config file, by name
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+pub struct Enums(HashMap<String, Enumeration>);
+
+impl Enums {
+    /// Find an enumeration by name, ignoring case if there is no exact match
+    pub fn get(&self, name: &str) -> Option<(&str, &Enumeration)> {
+        self.0
+            .get_key_value(name)
+            .or_else(|| {
+                self.0
+                    .iter()
+                    .find(|(set, _)| set.eq_ignore_ascii_case(name))
+            })
+            .map(|(name, enumeration)| (name.as_str(), enumeration))
+    }
+
+    /// Get the names of all enumerations
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// Find the value of a variant of any enumeration, by order of their names
+    fn variant(&self, name: &str) -> Option<IntermediateValue> {
+        let mut enums: Vec<(&String, &Enumeration)> = self.0.iter().collect();
+        enums.sort_by_key(|(name, _)| *name);
+        enums
+            .into_iter()
+            .find_map(|(_, enumeration)| enumeration.value(name))
+    }
+}
+
+impl<const N: usize> From<[(&str, Enumeration); N]> for Enums {
+    fn from(value: [(&str, Enumeration); N]) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|(name, enumeration)| (name.to_string(), enumeration))
+                .collect(),
+        )
+    }
+}
+
+/// Names of the values of an enumeration
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+#[serde(try_from = "HashMap<String, String>")]
+pub struct Enumeration(HashMap<IntermediateValue, String>);
+
+impl TryFrom<HashMap<String, String>> for Enumeration {
+    type Error = String;
+
+    fn try_from(value: HashMap<String, String>) -> Result<Self, Self::Error> {
+        // Sorted so that errors don't depend on the order of the map
+        let mut entries: Vec<(String, String)> = value.into_iter().collect();
+        entries.sort_by(|a, b| {
+            a.1.to_ascii_uppercase()
+                .cmp(&b.1.to_ascii_uppercase())
+                .then(a.cmp(b))
+        });
+
+        // Variants are looked up ignoring case, so they must differ by more than case
+        if let Some(pair) = entries
+            .windows(2)
+            .find(|pair| pair[0].1.eq_ignore_ascii_case(&pair[1].1))
+        {
+            let (a, b) = (&pair[0].1, &pair[1].1);
+            return Err(if a == b {
+                format!("Variant {} has more than one value", a)
+            } else {
+                format!("Variants {} and {} differ only in case", a, b)
+            });
+        }
+
+        let mut variants = HashMap::new();
+        for (value, name) in entries {
+            let inconv =
+                InputConverterType::from_notation(&value).unwrap_or(InputConverterType::DEC);
+            let value = inconv
+                .get_converter()
+                .convert(&value)
+                .map_err(|_| format!("Invalid value of {}: {}", name, value))?;
+            if let Some(other) = variants.insert(value, name.clone()) {
+                return Err(format!(
+                    "Variants {} and {} have the same value {}",
+                    other, name, value
+                ));
+            }
+        }
+        Ok(Self(variants))
+    }
+}
+
+impl Enumeration {
+    /// Get enumeration by name, empty if it is not declared
+    fn named(name: &str) -> &'static Self {
+        enums().get(name).map_or_else(
+            || EMPTY_ENUM.get_or_init(Self::default),
+            |(_, enumeration)| enumeration,
+        )
+    }
+
+    /// Find the value of a variant by name, ignoring case
+    ///
+    /// Variants differ by more than case, so at most one matches
+    fn value(&self, name: &str) -> Option<IntermediateValue> {
+        self.0
+            .iter()
+            .find(|(_, variant)| variant.eq_ignore_ascii_case(name))
+            .map(|(value, _)| *value)
+    }
+}
+
+/// Input converter that resolves the names of constants and enumeration values
+pub struct SymbolInputConverter<'a> {
+    pub constants: &'a Constants,
+    pub enums: &'a Enums,
+}
+
+impl Default for SymbolInputConverter<'static> {
+    /// Get converter for the symbols declared in the config file
+    fn default() -> Self {
+        Self {
+            constants: constants(),
+            enums: enums(),
+        }
+    }
+}
+
+impl InputConverter for SymbolInputConverter<'_> {
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        if input.is_empty() {
+            return Err(InputError::Empty);
+        }
+
+        self.constants
+            .get(input)
+            .or_else(|| self.enums.variant(input))
+            .ok_or_else(|| InputError::UnknownSymbol(input.to_string()))
+    }
+}
+
+/// Output converter that names the values of an enumeration
+pub struct EnumOutputConverter<'a> {
+    pub name: &'a str,
+    pub enumeration: &'a Enumeration,
+}
+
+impl EnumOutputConverter<'static> {
+    /// Get converter for an enumeration declared in the config file
+    pub fn named(name: &'static str) -> Self {
+        Self {
+            name,
+            enumeration: Enumeration::named(name),
+        }
+    }
+}
+
+impl OutputConverter for EnumOutputConverter<'_> {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        self.enumeration
+            .0
+            .get(&input)
+            .cloned()
+            .ok_or(OutputError::NotInEnum)
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        Some(match self.convert(input) {
+            Ok(variant) => vec![format!("{} is named {} in {}", input, variant, self.name)],
+            Err(err) => vec![err.to_string()],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opcode() -> Enumeration {
+        toml::from_str(
+            r#"
+            0x01 = "NOP"
+            0x02 = "JMP"
+            0b11 = "HALT"
+            -1 = "INVALID"
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn constants_deserialize_ok() {
+        let constants: Constants = toml::from_str(
+            r#"
+            PAGE_SIZE = 0x1000
+            MIN = -5
+            MASK = "0xFFFFFFFFFFFFFFFFFFFF"
+            LIMIT = "-100000000000000000000"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            constants,
+            Constants::from([
+                ("PAGE_SIZE", 4096),
+                ("MIN", -5),
+                ("MASK", 0xFFFFFFFFFFFFFFFFFFFF),
+                ("LIMIT", -100000000000000000000),
+            ])
+        );
+    }
+
+    #[test]
+    fn constants_deserialize_err() {
+        let tests = ["SIZE = \"0xZ\"", "SIZE = 1\nsize = 2", "SIZE = true"];
+        for input in tests {
+            toml::from_str::<Constants>(input).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn enumeration_deserialize_err() {
+        let tests = [
+            "x = \"NOP\"",
+            "0x = \"NOP\"",
+            "1 = 2",
+            "0x01 = \"NOP\"\n1 = \"JMP\"",
+            "1 = \"NOP\"\n2 = \"nop\"",
+            "1 = \"NOP\"\n2 = \"NOP\"",
+        ];
+        for input in tests {
+            toml::from_str::<Enumeration>(input).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn enum_outconv_ok() {
+        let enumeration = opcode();
+        let converter = EnumOutputConverter {
+            name: "opcode",
+            enumeration: &enumeration,
+        };
+        let tests = [(1, "NOP"), (3, "HALT"), (-1, "INVALID")];
+        for (input, exp) in tests {
+            assert_eq!(converter.convert(input).unwrap(), exp);
+        }
+        assert_eq!(converter.convert(4), Err(OutputError::NotInEnum));
+        assert_eq!(
+            converter.explain(3).unwrap(),
+            vec!["3 is named HALT in opcode"]
+        );
+    }
+
+    #[test]
+    fn symbol_inconv_ok() {
+        let constants = Constants::from([("PAGE_SIZE", 4096), ("page_shift", 12), ("MIN", -5)]);
+        let enums = Enums::from([("opcode", opcode())]);
+        let converter = SymbolInputConverter {
+            constants: &constants,
+            enums: &enums,
+        };
+        let tests = [
+            ("PAGE_SIZE", 4096),
+            ("page_size", 4096),
+            ("PAGE_SHIFT", 12),
+            ("MIN", -5),
+            ("JMP", 2),
+            ("halt", 3),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(converter.convert(input).unwrap(), exp);
+        }
+
+        let tests = [
+            ("", InputError::Empty),
+            ("PAGE", InputError::UnknownSymbol("PAGE".to_string())),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(converter.convert(input), Err(exp));
+        }
+    }
+}
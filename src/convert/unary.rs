@@ -0,0 +1,191 @@
+use super::{InputConverter, InputError, IntermediateValue, OutputConverter, OutputError};
+
+/// Input converter that accepts one-hot codes, with only the bit of the value set
+pub struct OneHotInputConverter;
+
+impl InputConverter for OneHotInputConverter {
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        let (prefix, bits) = split_bits(input)?;
+
+        // Exactly one bit set, whose position is the value
+        let mut set = None;
+        for (i, c) in bits.chars().enumerate() {
+            match c {
+                '0' => {}
+                '1' if set.is_none() => set = Some(bits.len() - 1 - i),
+                _ => return Err(InputError::InvalidCharacter(prefix.len() + i, c)),
+            }
+        }
+        set.map(|position| position as IntermediateValue)
+            .ok_or(InputError::NoBitSet)
+    }
+}
+
+/// Output converter that gives one-hot codes, with only the bit of the value set
+pub struct OneHotOutputConverter;
+
+impl OutputConverter for OneHotOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        // Don't encode negative values
+        if input < 0 {
+            return Err(OutputError::Negative);
+        }
+        if input >= IntermediateValue::BITS as IntermediateValue {
+            return Err(OutputError::DoesNotFit(IntermediateValue::BITS));
+        }
+        Ok(format!("1{}", "0".repeat(input as usize)))
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        Some(match self.convert(input) {
+            Ok(code) => vec![format!("1 << {} = {}", input, code)],
+            Err(err) => vec![err.to_string()],
+        })
+    }
+}
+
+/// Input converter that accepts thermometer codes, with as many lowest bits set as the value
+pub struct ThermometerInputConverter;
+
+impl InputConverter for ThermometerInputConverter {
+    fn convert(&self, input: &str) -> Result<IntermediateValue, InputError> {
+        let (prefix, bits) = split_bits(input)?;
+
+        // Zeros, then ones only
+        let mut ones = 0;
+        for (i, c) in bits.chars().enumerate() {
+            match c {
+                '0' if ones == 0 => {}
+                '1' => ones += 1,
+                _ => return Err(InputError::InvalidCharacter(prefix.len() + i, c)),
+            }
+        }
+        Ok(ones)
+    }
+}
+
+/// Output converter that gives thermometer codes, with as many lowest bits set as the value
+pub struct ThermometerOutputConverter;
+
+impl OutputConverter for ThermometerOutputConverter {
+    fn convert(&self, input: IntermediateValue) -> Result<String, OutputError> {
+        // Don't encode negative values
+        if input < 0 {
+            return Err(OutputError::Negative);
+        }
+        match input {
+            0 => Ok("0".to_string()),
+            1..=128 => Ok("1".repeat(input as usize)),
+            _ => Err(OutputError::DoesNotFit(IntermediateValue::BITS)),
+        }
+    }
+
+    fn explain(&self, input: IntermediateValue) -> Option<Vec<String>> {
+        Some(match self.convert(input) {
+            Ok(code) => vec![format!("(1 << {}) - 1 = {}", input, code)],
+            Err(err) => vec![err.to_string()],
+        })
+    }
+}
+
+/// Split optional 0b prefix from the bits of an input value
+fn split_bits(input: &str) -> Result<(&str, &str), InputError> {
+    let prefix = match input.get(..2) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("0b") => prefix,
+        _ => "",
+    };
+    match (prefix, &input[prefix.len()..]) {
+        ("", "") => Err(InputError::Empty),
+        (prefix, "") => Err(InputError::EmptyAfterPrefix(prefix.to_string())),
+        (prefix, bits) => Ok((prefix, bits)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unary_inconv_ok() {
+        let tests: [(&dyn InputConverter, &str, IntermediateValue); 6] = [
+            (&OneHotInputConverter, "1", 0),
+            (&OneHotInputConverter, "0b1000", 3),
+            (&OneHotInputConverter, "00100", 2),
+            (&ThermometerInputConverter, "0", 0),
+            (&ThermometerInputConverter, "0b111", 3),
+            (&ThermometerInputConverter, "0011", 2),
+        ];
+        for (conv, input, exp) in tests {
+            assert_eq!(conv.convert(input).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn unary_inconv_err() {
+        let tests: [(&dyn InputConverter, &str, InputError); 6] = [
+            (&OneHotInputConverter, "", InputError::Empty),
+            (&OneHotInputConverter, "000", InputError::NoBitSet),
+            (
+                &OneHotInputConverter,
+                "0b1010",
+                InputError::InvalidCharacter(4, '1'),
+            ),
+            (
+                &ThermometerInputConverter,
+                "0b",
+                InputError::EmptyAfterPrefix("0b".to_string()),
+            ),
+            (
+                &ThermometerInputConverter,
+                "0110",
+                InputError::InvalidCharacter(3, '0'),
+            ),
+            (
+                &ThermometerInputConverter,
+                "012",
+                InputError::InvalidCharacter(2, '2'),
+            ),
+        ];
+        for (conv, input, exp) in tests {
+            assert_eq!(conv.convert(input), Err(exp));
+        }
+    }
+
+    #[test]
+    fn unary_outconv_ok() {
+        let tests: [(&dyn OutputConverter, IntermediateValue, &str); 4] = [
+            (&OneHotOutputConverter, 0, "1"),
+            (&OneHotOutputConverter, 3, "1000"),
+            (&ThermometerOutputConverter, 0, "0"),
+            (&ThermometerOutputConverter, 3, "111"),
+        ];
+        for (conv, input, exp) in tests {
+            assert_eq!(conv.convert(input).unwrap(), exp);
+        }
+
+        let tests: [(&dyn OutputConverter, IntermediateValue, OutputError); 3] = [
+            (&OneHotOutputConverter, -1, OutputError::Negative),
+            (&OneHotOutputConverter, 128, OutputError::DoesNotFit(128)),
+            (
+                &ThermometerOutputConverter,
+                129,
+                OutputError::DoesNotFit(128),
+            ),
+        ];
+        for (conv, input, exp) in tests {
+            assert_eq!(conv.convert(input), Err(exp));
+        }
+    }
+
+    #[test]
+    fn unary_outconv_explain() {
+        assert_eq!(
+            OneHotOutputConverter.explain(3).unwrap(),
+            vec!["1 << 3 = 1000"]
+        );
+        assert_eq!(
+            ThermometerOutputConverter.explain(3).unwrap(),
+            vec!["(1 << 3) - 1 = 111"]
+        );
+    }
+}
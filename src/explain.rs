@@ -0,0 +1,521 @@
+use crate::convert::{
+    fitting_width, BitOperation, BitSlice, InputConverterType, IntermediateValue,
+    OutputConverterType,
+};
+
+/// Step-by-step derivation of the conversions from one input converter
+#[derive(Debug, PartialEq)]
+pub struct Explanation {
+    pub inconv: InputConverterType,
+    /// Steps reading the input value
+    pub input_steps: Vec<String>,
+    /// Steps deriving the value of each output converter
+    pub outputs: Vec<(OutputConverterType, Vec<String>)>,
+}
+
+impl Explanation {
+    /// Explain how a value read by an input converter is turned into each output
+    pub fn new<'a>(
+        inconv: InputConverterType,
+        outconvs: impl IntoIterator<Item = &'a OutputConverterType>,
+        input: &str,
+        value: IntermediateValue,
+        ops: &[BitOperation],
+        width: Option<u32>,
+    ) -> Self {
+        // The digits of a value with a bit range selected no longer match the result
+        let (mut input_steps, digits) = match BitSlice::split(input) {
+            Some(slice) => (explain_slice(inconv, &slice), None),
+            None => (explain_input(inconv, input, value), Some(input)),
+        };
+
+        // Nor do they after an operation
+        let mut value = value;
+        for op in ops {
+            let Ok(res) = op.apply(value, width) else {
+                break;
+            };
+            input_steps.push(format!("{}: {} becomes {}", op, value, res));
+            value = res;
+        }
+        let digits = digits.filter(|_| ops.is_empty());
+
+        let outputs = outconvs
+            .into_iter()
+            .filter(|outconv| digits.is_none() || !inconv.is_outconv_excluded(outconv))
+            .map(|outconv| {
+                let steps = outconv
+                    .get_converter(width)
+                    .explain(value)
+                    .unwrap_or_else(|| explain_output(inconv, *outconv, digits, value));
+                (*outconv, steps)
+            })
+            .collect();
+
+        Self {
+            inconv,
+            input_steps,
+            outputs,
+        }
+    }
+}
+
+/// Explain how the input value is read
+fn explain_input(inconv: InputConverterType, input: &str, value: IntermediateValue) -> Vec<String> {
+    match (inconv, inconv.radix()) {
+        (InputConverterType::ASCII, _) => vec![format!("{:?} has ASCII code {}", input, value)],
+        (InputConverterType::SYMBOL, _) => vec![format!("{} is defined as {}", input, value)],
+        (InputConverterType::MASK, _) => vec![format!(
+            "bits {} set: 0x{:X} = {}",
+            input, value as u128, value
+        )],
+        (InputConverterType::GRAY, _) => vec![format!(
+            "each bit of {} XORed with the bits above it: {:b} = {}",
+            strip_notation(input, 2),
+            value,
+            value
+        )],
+        (InputConverterType::BCD | InputConverterType::UBCD, _) => vec![format!(
+            "nibbles of {} hold the decimal digits of {}",
+            strip_notation(input, 16),
+            value
+        )],
+        (InputConverterType::XS3, _) => vec![format!(
+            "nibbles of {} minus 3 hold the decimal digits of {}",
+            strip_notation(input, 16),
+            value
+        )],
+        (InputConverterType::ONEHOT, _) => vec![format!("only bit {} is set", value)],
+        (InputConverterType::THERMO, _) => vec![format!("{} lowest bits are set", value)],
+        (_, Some(radix)) if radix != 10 => {
+            positional_expansion(strip_notation(input, radix), radix)
+        }
+        _ => vec![],
+    }
+}
+
+/// Explain how the input value is read, then how its bit range is extracted or replaced
+fn explain_slice(inconv: InputConverterType, slice: &BitSlice) -> Vec<String> {
+    let Ok(value) = inconv.get_converter().convert(slice.value) else {
+        return vec![];
+    };
+    let Ok(range) = slice.bit_range() else {
+        return vec![];
+    };
+
+    let mut steps = explain_input(inconv, slice.value, value);
+    let bits = value as u128;
+    let mask = range.mask() >> range.low;
+    match slice.replacement_value(range) {
+        Some(Ok(replacement)) => steps.push(format!(
+            "bits {} set to {}: ({} & !0x{:X}) | ({} << {}) = {}",
+            slice.range,
+            replacement,
+            value,
+            range.mask(),
+            replacement,
+            range.low,
+            range.insert(bits, replacement) as IntermediateValue
+        )),
+        Some(Err(_)) => {}
+        None => steps.push(format!(
+            "bits {}: ({} >> {}) & 0x{:X} = {}",
+            slice.range,
+            value,
+            range.low,
+            mask,
+            range.extract(bits)
+        )),
+    }
+    steps
+}
+
+/// Explain how a value is written in a numeric base
+///
+/// Digits of the input value are converted one by one between bases that allow it
+fn explain_output(
+    inconv: InputConverterType,
+    outconv: OutputConverterType,
+    digits: Option<&str>,
+    value: IntermediateValue,
+) -> Vec<String> {
+    let radix = match (outconv, outconv.radix()) {
+        (OutputConverterType::DEC, _) => return vec![value.to_string()],
+        (_, Some(radix)) => radix,
+        (_, None) => return vec![],
+    };
+
+    if value < 0 {
+        return twos_complement(value, radix);
+    }
+
+    // Bases that are powers of each other convert digit by digit
+    match (digits, inconv.radix(), radix) {
+        (Some(digits), Some(2), 8 | 16) => group_bits(strip_notation(digits, 2), radix),
+        (Some(digits), Some(from @ (8 | 16)), 2) => {
+            expand_digits(strip_notation(digits, from), from)
+        }
+        _ => repeated_division(value, radix),
+    }
+}
+
+/// Strip the prefix or suffix of a base from an input value read in it. Ex: 0x2A, 2Ah
+///
+/// Notations of other bases are digits, as 0B in the hexadecimal 0B1h
+fn strip_notation(input: &str, radix: u32) -> &str {
+    let prefix = match radix {
+        2 => "0b",
+        8 => "0o",
+        16 => "0x",
+        _ => return input,
+    };
+    match input.get(..2) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => &input[2..],
+        _ if radix == 16 => input.strip_suffix(['h', 'H']).unwrap_or(input),
+        _ => input,
+    }
+}
+
+/// Digit of a given value, in upper case
+fn digit(value: u32) -> char {
+    char::from_digit(value, 16)
+        .unwrap_or('?')
+        .to_ascii_uppercase()
+}
+
+/// Digit value, with its decimal value when it differs. Ex: A(10)
+fn digit_with_value(c: char, radix: u32) -> String {
+    match c.to_digit(radix) {
+        Some(value) if value >= 10 => format!("{}({})", c.to_ascii_uppercase(), value),
+        _ => c.to_string(),
+    }
+}
+
+/// Sum of the digits multiplied by powers of the radix
+///
+/// Ex: 2A = 2*16^1 + A(10)*16^0 = 32 + 10 = 42
+fn positional_expansion(digits: &str, radix: u32) -> Vec<String> {
+    let count = digits.chars().count();
+    let terms: Vec<(char, usize)> = digits
+        .chars()
+        .enumerate()
+        .map(|(i, c)| (c, count - i - 1))
+        .collect();
+
+    let powers: Vec<String> = terms
+        .iter()
+        .map(|(c, power)| format!("{}*{}^{}", digit_with_value(*c, radix), radix, power))
+        .collect();
+    let products: Vec<IntermediateValue> = terms
+        .iter()
+        .map(|(c, power)| {
+            let digit = c.to_digit(radix).unwrap_or(0) as IntermediateValue;
+            digit * (radix as IntermediateValue).pow(*power as u32)
+        })
+        .collect();
+    let sum: IntermediateValue = products.iter().sum();
+
+    let indent = " ".repeat(count + 1);
+    vec![
+        format!("{} = {}", digits, powers.join(" + ")),
+        format!(
+            "{}= {}",
+            indent,
+            products
+                .iter()
+                .map(|product| product.to_string())
+                .collect::<Vec<String>>()
+                .join(" + ")
+        ),
+        format!("{}= {}", indent, sum),
+    ]
+}
+
+/// Divisions by the radix, whose remainders are the digits from the last one
+fn repeated_division(value: IntermediateValue, radix: u32) -> Vec<String> {
+    let radix_value = radix as IntermediateValue;
+    let mut steps = vec![];
+    let mut digits = vec![];
+    let mut quotient = value;
+
+    loop {
+        let (next, remainder) = (quotient / radix_value, (quotient % radix_value) as u32);
+        let remainder_str = if remainder >= 10 {
+            format!("{} ({})", remainder, digit(remainder))
+        } else {
+            remainder.to_string()
+        };
+        steps.push(format!(
+            "{} / {} = {} remainder {}",
+            quotient, radix, next, remainder_str
+        ));
+        digits.push(digit(remainder));
+
+        quotient = next;
+        if quotient == 0 {
+            break;
+        }
+    }
+
+    steps.push(format!(
+        "remainders from last to first: {}",
+        digits.iter().rev().collect::<String>()
+    ));
+    steps
+}
+
+/// Groups of bits, from the right, each making a digit of a power of two radix
+fn group_bits(bits: &str, radix: u32) -> Vec<String> {
+    let size = radix.trailing_zeros() as usize;
+    let padded = format!("{}{}", "0".repeat((size - bits.len() % size) % size), bits);
+    let groups: Vec<&str> = (0..padded.len())
+        .step_by(size)
+        .map(|i| &padded[i..i + size])
+        .collect();
+    let digits: Vec<char> = groups
+        .iter()
+        .map(|group| digit(u32::from_str_radix(group, 2).unwrap_or(0)))
+        .collect();
+
+    vec![
+        format!(
+            "group bits by {} from the right: {}",
+            size,
+            groups.join(" ")
+        ),
+        format!(
+            "each group is a digit: {}",
+            digits
+                .iter()
+                .map(char::to_string)
+                .collect::<Vec<String>>()
+                .join(" ")
+        ),
+        format!("without leading zeros: {}", strip_leading_zeros(digits)),
+    ]
+}
+
+/// Digits of a power of two radix, each expanded to a group of bits
+fn expand_digits(digits: &str, radix: u32) -> Vec<String> {
+    let size = radix.trailing_zeros() as usize;
+    let groups: Vec<(char, String)> = digits
+        .chars()
+        .map(|c| {
+            let value = c.to_digit(radix).unwrap_or(0);
+            (c.to_ascii_uppercase(), format!("{:0size$b}", value))
+        })
+        .collect();
+    let bits: String = groups.iter().map(|(_, group)| group.as_str()).collect();
+
+    vec![
+        format!(
+            "each digit is {} bits: {}",
+            size,
+            groups
+                .iter()
+                .map(|(c, group)| format!("{} = {}", c, group))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        format!("joined: {}", bits),
+        format!(
+            "without leading zeros: {}",
+            strip_leading_zeros(bits.chars())
+        ),
+    ]
+}
+
+/// Two's complement of a negative value, on the smallest width that fits it
+fn twos_complement(value: IntermediateValue, radix: u32) -> Vec<String> {
+    let width = fitting_width(value);
+    let magnitude = value.unsigned_abs();
+    let mask = u128::MAX >> (128 - width);
+    let inverted = !magnitude & mask;
+    let complement = inverted.wrapping_add(1) & mask;
+
+    let w = width as usize;
+    let mut steps = vec![
+        format!(
+            "{} is negative: use two's complement on {} bits",
+            value, width
+        ),
+        format!("{} in binary: {:0w$b}", magnitude, magnitude),
+        format!("invert all bits: {:0w$b}", inverted),
+        format!("add 1: {:0w$b}", complement),
+    ];
+    if radix != 2 {
+        steps.extend(group_bits(&format!("{:0w$b}", complement), radix));
+    }
+    steps.push("skipped in normal output, as it depends on the width".to_string());
+    steps
+}
+
+/// Remove leading zero digits, keeping at least one
+fn strip_leading_zeros(digits: impl IntoIterator<Item = char>) -> String {
+    let digits: String = digits.into_iter().collect();
+    match digits.trim_start_matches('0') {
+        "" => "0".to_string(),
+        digits => digits.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_notation_ok() {
+        let tests = [
+            ("0x2A", 16, "2A"),
+            ("2Ah", 16, "2A"),
+            ("0b11h", 16, "0b11"),
+            ("0B1", 16, "0B1"),
+            ("0b101", 2, "101"),
+            ("0o17", 8, "17"),
+            ("0x17", 8, "0x17"),
+            ("42", 10, "42"),
+        ];
+        for (input, radix, exp) in tests {
+            assert_eq!(strip_notation(input, radix), exp);
+        }
+    }
+
+    #[test]
+    fn positional_expansion_ok() {
+        assert_eq!(
+            positional_expansion("2a", 16),
+            vec!["2a = 2*16^1 + A(10)*16^0", "   = 32 + 10", "   = 42"]
+        );
+        assert_eq!(
+            positional_expansion("101", 2),
+            vec!["101 = 1*2^2 + 0*2^1 + 1*2^0", "    = 4 + 0 + 1", "    = 5"]
+        );
+    }
+
+    #[test]
+    fn repeated_division_ok() {
+        let tests = [
+            (
+                42,
+                16,
+                vec![
+                    "42 / 16 = 2 remainder 10 (A)",
+                    "2 / 16 = 0 remainder 2",
+                    "remainders from last to first: 2A",
+                ],
+            ),
+            (
+                0,
+                2,
+                vec!["0 / 2 = 0 remainder 0", "remainders from last to first: 0"],
+            ),
+        ];
+        for (value, radix, exp) in tests {
+            assert_eq!(repeated_division(value, radix), exp);
+        }
+    }
+
+    #[test]
+    fn group_bits_ok() {
+        let tests = [
+            (
+                "101010",
+                16,
+                vec![
+                    "group bits by 4 from the right: 0010 1010",
+                    "each group is a digit: 2 A",
+                    "without leading zeros: 2A",
+                ],
+            ),
+            (
+                "0001111",
+                8,
+                vec![
+                    "group bits by 3 from the right: 000 001 111",
+                    "each group is a digit: 0 1 7",
+                    "without leading zeros: 17",
+                ],
+            ),
+        ];
+        for (bits, radix, exp) in tests {
+            assert_eq!(group_bits(bits, radix), exp);
+        }
+    }
+
+    #[test]
+    fn expand_digits_ok() {
+        assert_eq!(
+            expand_digits("2a", 16),
+            vec![
+                "each digit is 4 bits: 2 = 0010, A = 1010",
+                "joined: 00101010",
+                "without leading zeros: 101010",
+            ]
+        );
+    }
+
+    #[test]
+    fn twos_complement_ok() {
+        assert_eq!(
+            twos_complement(-5, 16),
+            vec![
+                "-5 is negative: use two's complement on 8 bits",
+                "5 in binary: 00000101",
+                "invert all bits: 11111010",
+                "add 1: 11111011",
+                "group bits by 4 from the right: 1111 1011",
+                "each group is a digit: F B",
+                "without leading zeros: FB",
+                "skipped in normal output, as it depends on the width",
+            ]
+        );
+        assert_eq!(
+            twos_complement(-129, 2)[0],
+            "-129 is negative: use two's complement on 16 bits"
+        );
+    }
+
+    #[test]
+    fn explain_slice_ok() {
+        let tests = [
+            (
+                "0xDEADBEEF[15:8]",
+                "bits 15:8: (3735928559 >> 8) & 0xFF = 190",
+            ),
+            (
+                "0xFF[7:4]=0b1010",
+                "bits 7:4 set to 10: (255 & !0xF0) | (10 << 4) = 175",
+            ),
+        ];
+        for (input, exp) in tests {
+            let slice = BitSlice::split(input).unwrap();
+            let steps = explain_slice(InputConverterType::HEX, &slice);
+            assert_eq!(steps.last().unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn explanation_ok() {
+        let outconvs = [OutputConverterType::DEC, OutputConverterType::HEX];
+        let explanation = Explanation::new(
+            InputConverterType::BIN,
+            &outconvs,
+            "0b101010",
+            42,
+            &[],
+            None,
+        );
+        assert_eq!(explanation.input_steps[2], "       = 42");
+        assert_eq!(
+            explanation.outputs[1],
+            (
+                OutputConverterType::HEX,
+                vec![
+                    "group bits by 4 from the right: 0010 1010".to_string(),
+                    "each group is a digit: 2 A".to_string(),
+                    "without leading zeros: 2A".to_string(),
+                ]
+            )
+        );
+    }
+}
@@ -1,17 +1,142 @@
 mod constants;
 mod convert;
+mod explain;
 mod opts;
+mod quiz;
+mod template;
 mod ui;
 
-use convert::{do_convert, ConversionError};
-pub use {opts::Opts, ui::ColorPalette};
+use std::io::{self, BufRead};
+
+use constants::{LITERAL_PREFIX, STDIN_INPUT};
+use convert::{
+    do_convert, do_convert_diagram, do_convert_explain, do_convert_raw, do_convert_table,
+    do_convert_template, ConversionError,
+};
+use opts::OutputMode;
+use quiz::run_quiz;
+use ui::terminal_width;
+pub use {
+    opts::Opts,
+    ui::{use_stderr_colors, ColorPalette},
+};
 
 /// Execute conversion and print results
 pub fn run(opts: Opts) -> Result<(), ConversionError> {
-    let output = do_convert(opts)?;
+    if let Some(settings) = &opts.quiz {
+        return run_quiz(settings, &opts);
+    }
+
+    let inputs = read_inputs(&opts.input)?;
+
+    match &opts.output_mode {
+        OutputMode::Pretty | OutputMode::Grid => {
+            let width = terminal_width();
+            for input in &inputs {
+                let output = do_convert(input, &opts)?;
+
+                // Print result, labelled with its input value when there are several
+                if inputs.len() > 1 {
+                    println!("{}", input.as_str().format_binary());
+                }
+                if opts.output_mode == OutputMode::Grid {
+                    print!("{}", output.grid(&opts.outconvs, width));
+                } else {
+                    print!("{}", output);
+                }
+            }
+        }
+        OutputMode::Raw => {
+            for input in &inputs {
+                println!("{}", do_convert_raw(input, &opts)?);
+            }
+        }
+        OutputMode::Template(template) => {
+            for input in &inputs {
+                let output = do_convert_template(input, &opts, template)?;
+                for line in output.lines {
+                    println!("{}", line);
+                }
 
-    // Print result
-    print!("{}", output);
+                // Interpretations left out are noted apart from the rendered values
+                for (inconv, outconv, err) in output.skipped {
+                    use_stderr_colors();
+                    eprintln!(
+                        "{}: {} to {}: skipped, {}",
+                        "note".format_note(),
+                        inconv,
+                        outconv,
+                        err
+                    );
+                }
+            }
+        }
+        OutputMode::Explain => {
+            for input in &inputs {
+                for explanation in do_convert_explain(input, &opts)? {
+                    print!("{}", explanation);
+                }
+            }
+        }
+        OutputMode::Diagram => {
+            for input in &inputs {
+                for diagram in do_convert_diagram(input, &opts)? {
+                    print!("{}", diagram);
+                }
+            }
+        }
+        OutputMode::Table(format) => {
+            let table = do_convert_table(&inputs, &opts);
+
+            // Print result
+            print!("{}", table.delimited(*format));
+        }
+    }
 
     Ok(())
 }
+
+/// Get input values to be converted
+fn read_inputs(input: &str) -> Result<Vec<String>, ConversionError> {
+    if input != STDIN_INPUT {
+        return Ok(vec![literal_value(input).to_string()]);
+    }
+
+    // Read one value per line, skipping empty lines
+    io::stdin()
+        .lock()
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.is_empty()))
+        .collect::<Result<_, _>>()
+        .map_err(ConversionError::ReadInput)
+}
+
+/// Strip the literal prefix from a value given on the command line
+///
+/// Ex: =dec is the value "dec", never the decimal input converter, and =- is the character '-'
+fn literal_value(input: &str) -> &str {
+    match input.strip_prefix(LITERAL_PREFIX) {
+        Some(value) if !value.is_empty() => value,
+        _ => input,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_value_ok() {
+        let tests = [
+            ("=dec", "dec"),
+            ("=-", "-"),
+            ("==", "="),
+            ("=", "="),
+            ("42", "42"),
+            ("a=b", "a=b"),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(literal_value(input), exp);
+        }
+    }
+}
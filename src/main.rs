@@ -1,6 +1,6 @@
 use std::{env, process};
 
-use baseic::{run, ColorPalette, Opts};
+use baseic::{run, use_stderr_colors, ColorPalette, Opts};
 
 fn main() {
     // Build run options
@@ -11,6 +11,7 @@ fn main() {
             process::exit(0);
         } else {
             // Print error
+            use_stderr_colors();
             eprintln!("{}: {}", "error".format_error(), err);
             process::exit(1);
         }
@@ -19,7 +20,8 @@ fn main() {
     // Run conversion
     run(opts).unwrap_or_else(|err| {
         // Print error
+        use_stderr_colors();
         eprintln!("{}: {}", "error".format_error(), err);
-        process::exit(2);
+        process::exit(err.exit_code());
     })
 }
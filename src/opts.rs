@@ -3,18 +3,26 @@ mod config;
 
 use args::{ArgParseError, ArgVals};
 use indexmap::{indexset, IndexSet};
-use std::{error::Error, path::PathBuf};
+use serde::{de, Deserialize};
+use std::{error::Error, path::PathBuf, str::FromStr};
 
 use crate::{
     constants::CONFIG_FILE_PATH,
-    convert::{InputConverterType, OutputConverterType},
+    convert::{
+        set_constants, set_enums, set_flag_sets, set_registers, AlreadyInUse, BitOperation,
+        FlagSets, InputConverterType, OutputConverterType, Registers, SvdError,
+    },
+    quiz::QuizSettings,
+    template::Template,
+    ui::{set_color_mode, set_theme, TableFormat},
 };
+pub(crate) use config::Aliases;
 use config::Config;
 
 /// Conversion options
 ///
 /// Contains input data and configuration
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
 pub struct Opts {
     // Input string to be converted
     pub input: String,
@@ -22,18 +30,117 @@ pub struct Opts {
     pub inconvs: IndexSet<InputConverterType>,
     // Vector of output converters that should be applied
     pub outconvs: IndexSet<OutputConverterType>,
+    // How results should be rendered
+    pub output_mode: OutputMode,
+    // Whether unlikely interpretations of the input should be shown too
+    pub show_all: bool,
+    // Width in bits of the values, for the converters that depend on it
+    pub width: Option<u32>,
+    // Operations applied to values after input conversion, in order
+    pub ops: Vec<BitOperation>,
+    // Quiz session to run instead of a conversion
+    pub quiz: Option<QuizSettings>,
+}
+
+/// Rendering style of conversion results
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum OutputMode {
+    /// One block of results per input converter
+    #[default]
+    Pretty,
+    /// Delimited table with one row per input value
+    Table(TableFormat),
+    /// Bare value of the only result
+    Raw,
+    /// Grid with input converters as rows and output converters as columns
+    Grid,
+    /// User-defined format template, one line per input converter
+    Template(Template),
+    /// Step-by-step derivation of each result
+    Explain,
+    /// Diagram of the bits of each interpretation
+    Diagram,
+}
+
+/// Layout of human readable conversion results
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Layout {
+    /// One block of results per input converter
+    List,
+    /// Single grid of all results
+    Grid,
+}
+
+impl From<Layout> for OutputMode {
+    fn from(value: Layout) -> Self {
+        match value {
+            Layout::List => Self::Pretty,
+            Layout::Grid => Self::Grid,
+        }
+    }
+}
+
+impl FromStr for Layout {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "list" => Ok(Self::List),
+            "grid" => Ok(Self::Grid),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Layout {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| de::Error::custom(format!("Invalid layout: {}", s)))
+    }
 }
 
 impl Opts {
     /// Build Opts from args and config file
-    pub fn build(args: &[String]) -> Result<Self, OptsBuildError> {
+    pub fn build(args: &[String]) -> Result<Self, OptsBuildError<'_>> {
         // Read config from config file
         let file_path = dirs::home_dir().unwrap().join(CONFIG_FILE_PATH);
-        let config = Config::from_file(&PathBuf::from(file_path))
-            .map_err(|err| OptsBuildError::Config(err))?;
+        let config = Config::from_file(&file_path).map_err(OptsBuildError::Config)?;
+
+        // Set up colours before anything gets printed
+        set_color_mode(config.color);
+        set_theme(config.theme.clone());
+
+        // Register layouts are needed to parse the converters
+        // An SVD file set on the command line takes precedence over the config file,
+        // whose relative paths start from its directory
+        let svd = ArgVals::svd_path(args, &config.aliases).or_else(|| {
+            let dir = file_path.parent()?;
+            config.svd.as_ref().map(|path| dir.join(path))
+        });
+        let mut registers = match svd {
+            Some(path) => {
+                Registers::from_svd_file(&path).map_err(|err| OptsBuildError::Svd(path, err))?
+            }
+            None => Registers::default(),
+        };
+        registers.merge(config.registers.clone());
+        set_registers(registers).map_err(OptsBuildError::Definitions)?;
+
+        // Flag sets of the config file replace the built-in ones with the same name
+        let mut flag_sets = FlagSets::builtin();
+        flag_sets.merge(config.flags.clone());
+        set_flag_sets(flag_sets).map_err(OptsBuildError::Definitions)?;
+
+        // Enumerations are needed to parse the converters, constants only to convert
+        set_constants(config.constants.clone()).map_err(OptsBuildError::Definitions)?;
+        set_enums(config.enums.clone()).map_err(OptsBuildError::Definitions)?;
 
         // Parse args
-        let argvals = ArgVals::from_args(args)?;
+        let argvals = ArgVals::from_args(args, &config.aliases)?;
 
         Ok(opts_build_internal(config, argvals))
     }
@@ -55,10 +162,23 @@ fn opts_build_internal(config: Config, args: ArgVals) -> Opts {
         config.default_inconvs.into_iter().collect()
     };
 
+    // Output mode options take precedence over layout, command line over config
+    let output_mode = match (args.output_mode, args.layout, config.format) {
+        (Some(output_mode), _, _) => output_mode,
+        (None, Some(layout), _) => layout.into(),
+        (None, None, Some(template)) => OutputMode::Template(template),
+        (None, None, None) => config.layout.into(),
+    };
+
     Opts {
         input: args.input,
         inconvs,
         outconvs,
+        output_mode,
+        show_all: args.show_all,
+        width: args.width,
+        ops: args.ops,
+        quiz: args.quiz,
     }
 }
 
@@ -66,16 +186,14 @@ fn opts_build_internal(config: Config, args: ArgVals) -> Opts {
 pub enum OptsBuildError<'a> {
     Args(ArgParseError<'a>),
     Config(Box<dyn Error>),
+    Svd(PathBuf, SvdError),
+    Definitions(AlreadyInUse),
 }
 
 impl<'a> OptsBuildError<'a> {
     /// Perform graceful exit?
     pub fn graceful_exit(&self) -> bool {
-        if let Self::Args(ArgParseError::GracefulExit) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Self::Args(ArgParseError::GracefulExit))
     }
 }
 
@@ -88,6 +206,11 @@ impl<'a> From<ArgParseError<'a>> for OptsBuildError<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        convert::{Constants, Enums},
+        ui::{ColorMode, Theme},
+    };
+    use config::Aliases;
 
     #[test]
     fn opts_build_internal_ok() {
@@ -104,11 +227,27 @@ mod tests {
                         InputConverterType::BIN,
                         InputConverterType::DEC,
                     ],
+                    layout: Layout::List,
+                    format: None,
+                    color: ColorMode::Auto,
+                    theme: Theme::default(),
+                    aliases: Aliases::default(),
+                    registers: Registers::default(),
+                    svd: None,
+                    flags: FlagSets::default(),
+                    constants: Constants::default(),
+                    enums: Enums::default(),
                 },
                 ArgVals {
                     input: "test123".to_string(),
                     inconv: None,
                     outconvs: None,
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
                 },
                 Opts {
                     input: "test123".to_string(),
@@ -122,6 +261,11 @@ mod tests {
                         OutputConverterType::BIN,
                         OutputConverterType::DEC,
                     ],
+                    output_mode: OutputMode::Pretty,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
                 },
             ),
             (
@@ -136,6 +280,16 @@ mod tests {
                         InputConverterType::BIN,
                         InputConverterType::DEC,
                     ],
+                    layout: Layout::List,
+                    format: None,
+                    color: ColorMode::Auto,
+                    theme: Theme::default(),
+                    aliases: Aliases::default(),
+                    registers: Registers::default(),
+                    svd: None,
+                    flags: FlagSets::default(),
+                    constants: Constants::default(),
+                    enums: Enums::default(),
                 },
                 ArgVals {
                     input: "test123".to_string(),
@@ -144,11 +298,59 @@ mod tests {
                         OutputConverterType::HEX,
                         OutputConverterType::BIN
                     ]),
+                    output_mode: Some(OutputMode::Table(TableFormat::CSV)),
+                    layout: Some(Layout::Grid),
+                    show_all: true,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
                 },
                 Opts {
                     input: "test123".to_string(),
                     inconvs: indexset![InputConverterType::BIN],
                     outconvs: indexset![OutputConverterType::HEX, OutputConverterType::BIN],
+                    output_mode: OutputMode::Table(TableFormat::CSV),
+                    show_all: true,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                Config {
+                    default_outconvs: vec![OutputConverterType::HEX],
+                    default_inconvs: vec![InputConverterType::DEC],
+                    layout: Layout::Grid,
+                    format: Some("{hex:04}".parse().unwrap()),
+                    color: ColorMode::Never,
+                    theme: Theme::default(),
+                    aliases: Aliases::default(),
+                    registers: Registers::default(),
+                    svd: None,
+                    flags: FlagSets::default(),
+                    constants: Constants::default(),
+                    enums: Enums::default(),
+                },
+                ArgVals {
+                    input: "test123".to_string(),
+                    inconv: None,
+                    outconvs: None,
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+                Opts {
+                    input: "test123".to_string(),
+                    inconvs: indexset![InputConverterType::DEC],
+                    outconvs: indexset![OutputConverterType::HEX],
+                    output_mode: OutputMode::Template("{hex:04}".parse().unwrap()),
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
                 },
             ),
         ];
@@ -1,45 +1,83 @@
-use std::{fmt::Display, str::FromStr};
+use std::{fmt::Display, path::PathBuf, str::FromStr};
 
 use indexmap::IndexSet;
 use nom::{
-    combinator::opt,
     error::{Error, ErrorKind, ParseError},
-    multi::many0,
     Err, IResult,
 };
+use strum::IntoEnumIterator;
 
 use crate::{
-    convert::{InputConverterType, OutputConverterType},
-    ui::{help, version},
+    constants::{
+        DEFAULT_QUIZ_COUNT, DEFAULT_QUIZ_WIDTH, ENUM_CONVERTER, FLAGS_CONVERTER, QUIZ_COMMAND,
+        REGISTER_CONVERTER,
+    },
+    convert::{enums, flag_sets, registers, BitOperation, InputConverterType, OutputConverterType},
+    quiz::QuizSettings,
+    template::Template,
+    ui::{help, set_color_mode, use_stderr_colors, version, ColorMode, TableFormat},
 };
 
+use super::{config::Aliases, Layout, OutputMode};
+
 /// Representation of the cli arguments
 #[derive(Debug, PartialEq)]
 pub struct ArgVals {
     pub input: String,
     pub inconv: Option<InputConverterType>,
     pub outconvs: Option<IndexSet<OutputConverterType>>,
+    pub output_mode: Option<OutputMode>,
+    pub layout: Option<Layout>,
+    pub show_all: bool,
+    pub width: Option<u32>,
+    pub ops: Vec<BitOperation>,
+    pub quiz: Option<QuizSettings>,
 }
 
 impl ArgVals {
     /// Parse args
     /// Args format: [OPTS] [INCONV] INPUT [OUTCONVS]
-    pub fn from_args(args: &[String]) -> Result<ArgVals, ArgParseError> {
-        parse_arguments(&args[1..])
+    pub fn from_args<'a>(
+        args: &'a [String],
+        aliases: &Aliases,
+    ) -> Result<ArgVals, ArgParseError<'a>> {
+        parse_arguments(&args[1..], aliases)
             .map(|(_, vals)| vals)
             .map_err(|e| match e {
                 Err::Incomplete(_) => panic!(),
                 Err::Failure(e) | Err::Error(e) => e,
             })
     }
+
+    /// Get the SVD file set on the command line
+    ///
+    /// Registers must be known before the arguments naming them are parsed
+    pub fn svd_path(args: &[String], aliases: &Aliases) -> Option<PathBuf> {
+        let (_, (_, opts)) = parse_cli_options(args.get(1..)?, aliases).ok()?;
+        opts.svd
+    }
 }
 
 /// Arugment parsing error
 #[derive(Debug, PartialEq)]
 pub enum ArgParseError<'a> {
     MissingInput,
-    UnknownOutputConverter(&'a str),
+    /// Unknown name, with the closest known name if any
+    UnknownOutputConverter(&'a str, Option<String>),
+    /// Unknown register name, with the closest declared name if any
+    UnknownRegister(&'a str, Option<String>),
+    MissingRegisterName,
+    /// Unknown flag set name, with the closest known name if any
+    UnknownFlagSet(&'a str, Option<String>),
+    MissingFlagSetName,
+    /// Unknown enumeration name, with the closest declared name if any
+    UnknownEnum(&'a str, Option<String>),
+    MissingEnumName,
     InvalidCliOption(&'a str),
+    MissingCliOptionValue(&'a str),
+    InvalidCliOptionValue(&'a str, &'a str),
+    QuizOptionWithoutQuiz,
+    UnexpectedArgument(&'a str),
     Eof,
     GracefulExit,
 }
@@ -49,6 +87,17 @@ pub enum ArgParseError<'a> {
 pub struct CliOptions {
     pub help: bool,
     pub version: bool,
+    pub show_all: bool,
+    pub output_mode: Option<OutputMode>,
+    pub layout: Option<Layout>,
+    pub color: Option<ColorMode>,
+    pub inconv: Option<InputConverterType>,
+    pub outconvs: Option<IndexSet<OutputConverterType>>,
+    pub seed: Option<u64>,
+    pub width: Option<u32>,
+    pub count: Option<usize>,
+    pub svd: Option<PathBuf>,
+    pub ops: Vec<BitOperation>,
 }
 
 impl CliOptions {
@@ -56,6 +105,17 @@ impl CliOptions {
         Self {
             help: false,
             version: false,
+            show_all: false,
+            output_mode: None,
+            layout: None,
+            color: None,
+            inconv: None,
+            outconvs: None,
+            seed: None,
+            width: None,
+            count: None,
+            svd: None,
+            ops: Vec::new(),
         }
     }
 }
@@ -64,14 +124,40 @@ impl CliOptions {
 enum CliOptionToken {
     Help,
     Version,
+    All,
+    Csv,
+    Tsv,
+    Raw,
+    Explain,
+    Diagram,
+    Layout(Layout),
+    Format(Template),
+    Color(ColorMode),
+    From(InputConverterType),
+    To(IndexSet<OutputConverterType>),
+    Seed(u64),
+    Width(u32),
+    Count(usize),
+    Svd(PathBuf),
+    Operation(BitOperation),
+    EndOfOptions,
 }
 
+/// Parse options that don't take a value
 impl FromStr for CliOptionToken {
     type Err = ();
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "-h" => Ok(Self::Help),
-            "-v" => Ok(Self::Version),
+            "-h" | "--help" => Ok(Self::Help),
+            "-v" | "--version" => Ok(Self::Version),
+            "-a" | "--all" => Ok(Self::All),
+            "--csv" => Ok(Self::Csv),
+            "--tsv" => Ok(Self::Tsv),
+            "-r" | "--raw" => Ok(Self::Raw),
+            "--explain" => Ok(Self::Explain),
+            "--diagram" => Ok(Self::Diagram),
+            "--reverse" => Ok(Self::Operation(BitOperation::Reverse)),
+            "--bswap" => Ok(Self::Operation(BitOperation::ByteSwap)),
             _ => Err(()),
         }
     }
@@ -81,12 +167,61 @@ impl<'a> Display for ArgParseError<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::MissingInput => write!(f, "input value is required"),
-            Self::UnknownOutputConverter(conv) => {
-                write!(f, "unknown ouptut converter: \"{}\"", conv)
+            Self::UnknownOutputConverter(conv, suggestion) => {
+                write!(f, "unknown output converter: \"{}\"", conv)?;
+                match suggestion {
+                    Some(name) => write!(f, ", did you mean \"{}\"?", name),
+                    None => Ok(()),
+                }
+            }
+            Self::UnknownRegister(name, suggestion) => {
+                write!(f, "unknown register: \"{}\"", name)?;
+                match suggestion {
+                    Some(name) => write!(f, ", did you mean \"{}\"?", name),
+                    None => write!(
+                        f,
+                        ", registers are declared in the config file or an SVD file"
+                    ),
+                }
+            }
+            Self::MissingRegisterName => {
+                write!(f, "missing register name after \"{}\"", REGISTER_CONVERTER)
+            }
+            Self::UnknownFlagSet(name, suggestion) => {
+                write!(f, "unknown flag set: \"{}\"", name)?;
+                match suggestion {
+                    Some(name) => write!(f, ", did you mean \"{}\"?", name),
+                    None => Ok(()),
+                }
+            }
+            Self::MissingFlagSetName => {
+                write!(f, "missing flag set name after \"{}\"", FLAGS_CONVERTER)
+            }
+            Self::UnknownEnum(name, suggestion) => {
+                write!(f, "unknown enumeration: \"{}\"", name)?;
+                match suggestion {
+                    Some(name) => write!(f, ", did you mean \"{}\"?", name),
+                    None => write!(f, ", enumerations are declared in the config file"),
+                }
+            }
+            Self::MissingEnumName => {
+                write!(f, "missing enumeration name after \"{}\"", ENUM_CONVERTER)
             }
             Self::InvalidCliOption(opt) => {
                 write!(f, "invalid cli option: \"{}\"", opt)
             }
+            Self::MissingCliOptionValue(opt) => {
+                write!(f, "missing value for cli option: \"{}\"", opt)
+            }
+            Self::InvalidCliOptionValue(opt, val) => {
+                write!(f, "invalid value for cli option \"{}\": \"{}\"", opt, val)
+            }
+            Self::QuizOptionWithoutQuiz => {
+                write!(f, "--seed and --count only apply to the quiz command")
+            }
+            Self::UnexpectedArgument(arg) => {
+                write!(f, "unexpected argument: \"{}\"", arg)
+            }
             Self::Eof => {
                 write!(f, "missing required argument")
             }
@@ -99,88 +234,542 @@ impl<'a> Display for ArgParseError<'a> {
 }
 
 /// Parse arguments
-fn parse_arguments(input: &[String]) -> IResult<&[String], ArgVals, ArgParseError> {
-    // Parse options
-    let (input, opts) = parse_cli_options(input).expect("should never fail");
+fn parse_arguments<'a>(
+    input: &'a [String],
+    aliases: &Aliases,
+) -> IResult<&'a [String], ArgVals, ArgParseError<'a>> {
+    // Parse options, leaving the positional arguments
+    let (_, (args, opts)) = parse_cli_options(input, aliases)?;
+    let input = args.as_slice();
+
+    // Quiz command, taking no value
+    if let Some((_, input)) = input.split_first().filter(|(cmd, _)| **cmd == QUIZ_COMMAND) {
+        return parse_quiz(input, opts);
+    }
+    if opts.seed.is_some() || opts.count.is_some() {
+        return Err(Err::Error(ArgParseError::QuizOptionWithoutQuiz));
+    }
 
     // Act upon options
+    let output_mode = opts.output_mode.clone();
+    let layout = opts.layout;
+    let (show_all, width, ops) = (opts.show_all, opts.width, opts.ops.clone());
+    let (opt_inconv, opt_outconvs) = (opts.inconv, opts.outconvs.clone());
     handle_options(opts)?;
 
-    // Parse arguments
-    let (input, inconv) =
-        opt(parse_fromstr::<InputConverterType>)(input).map_err(|_| panic!("optional"))?;
+    // Parse arguments, the input converter is not positional if set by an option
+    // A lone token is always the value, even if it names an input converter
+    let (input, inconv) = if opt_inconv.is_some() || input.len() == 1 {
+        (input, opt_inconv)
+    } else if input
+        .first()
+        .is_some_and(|arg| arg.eq_ignore_ascii_case(REGISTER_CONVERTER))
+    {
+        let (input, name) = parse_register_name(&input[1..])?;
+        (input, Some(InputConverterType::REG(name)))
+    } else if input
+        .first()
+        .is_some_and(|arg| arg.eq_ignore_ascii_case(FLAGS_CONVERTER))
+    {
+        let (input, name) = parse_flag_set_name(&input[1..])?;
+        (input, Some(InputConverterType::FLAGS(name)))
+    } else {
+        parse_positional_inconv(input, aliases)
+    };
     let (input, inval) = any(input).map_err(|_| Err::Error(ArgParseError::MissingInput))?;
-    let (input, outconvs) = parse_outconvs_list(input)?;
+    let (_, outconvs) = parse_outconvs_list(input, aliases)?;
+
+    // Output converters set by options come first
+    let outconvs = match (opt_outconvs, outconvs) {
+        (Some(opt_outconvs), Some(outconvs)) => {
+            Some(opt_outconvs.union(&outconvs).copied().collect())
+        }
+        (opt_outconvs, outconvs) => opt_outconvs.or(outconvs),
+    };
 
     Ok((
-        input,
+        &[],
         ArgVals {
-            input: inval.clone(),
+            input: inval.to_string(),
             inconv,
             outconvs,
+            output_mode,
+            layout,
+            show_all,
+            width,
+            ops,
+            quiz: None,
+        },
+    ))
+}
+
+/// Parse arguments of the quiz command, which takes none
+fn parse_quiz<'a>(
+    input: &[&'a str],
+    opts: CliOptions,
+) -> IResult<&'a [String], ArgVals, ArgParseError<'a>> {
+    if let Some(arg) = input.first() {
+        return Err(Err::Error(ArgParseError::UnexpectedArgument(arg)));
+    }
+
+    let quiz = QuizSettings {
+        seed: opts.seed,
+        width: opts.width.unwrap_or(DEFAULT_QUIZ_WIDTH),
+        count: opts.count.unwrap_or(DEFAULT_QUIZ_COUNT),
+    };
+    let (inconv, outconvs) = (opts.inconv, opts.outconvs.clone());
+    let (show_all, width) = (opts.show_all, opts.width);
+    handle_options(opts)?;
+
+    Ok((
+        &[],
+        ArgVals {
+            input: String::new(),
+            inconv,
+            outconvs,
+            output_mode: None,
+            layout: None,
+            show_all,
+            width,
+            ops: Vec::new(),
+            quiz: Some(quiz),
         },
     ))
 }
 
 fn handle_options<'a>(opts: CliOptions) -> IResult<(), (), ArgParseError<'a>> {
+    // Override colours from config file
+    if let Some(color) = opts.color {
+        set_color_mode(color);
+    }
     // Check if -h option set
     if opts.help {
         // Print help
+        use_stderr_colors();
         eprintln!("{}", help());
         return Err(Err::Error(ArgParseError::GracefulExit));
     }
     // Check if -v option set
     if opts.version {
-        // Print help
+        // Print version
+        use_stderr_colors();
         eprintln!("{}", version());
         return Err(Err::Error(ArgParseError::GracefulExit));
     }
     Ok(((), ()))
 }
 
-/// Parse CLI options
-fn parse_cli_options(input: &[String]) -> IResult<&[String], CliOptions> {
-    let (input, opt_tokens) = many0(parse_cli_opt)(input)?;
-
-    // Extract settings from options
+/// Parse CLI options, GNU-style: anywhere before --
+///
+/// Gives the positional arguments left, in order
+fn parse_cli_options<'a>(
+    mut input: &'a [String],
+    aliases: &Aliases,
+) -> IResult<&'a [String], (Vec<&'a str>, CliOptions), ArgParseError<'a>> {
     let mut opts = CliOptions::new();
-    for opt in opt_tokens {
-        match opt {
-            CliOptionToken::Help => opts.help = true,
-            CliOptionToken::Version => opts.version = true,
+    let mut args = Vec::new();
+
+    while let Some(token) = input.first() {
+        let opt_tokens = match parse_cli_opt(input, aliases) {
+            Ok((rem, opt_tokens)) => {
+                input = rem;
+                opt_tokens
+            }
+            // Not an option, but a positional argument
+            Err(Err::Error(_)) => {
+                args.push(token.as_str());
+                input = &input[1..];
+                continue;
+            }
+            // Invalid option
+            Err(Err::Failure(e)) => return Err(Err::Error(e)),
+            Err(Err::Incomplete(_)) => panic!(),
+        };
+
+        // Extract settings from options
+        for opt in opt_tokens {
+            match opt {
+                CliOptionToken::Help => opts.help = true,
+                CliOptionToken::Version => opts.version = true,
+                CliOptionToken::All => opts.show_all = true,
+                CliOptionToken::Csv => opts.output_mode = Some(OutputMode::Table(TableFormat::CSV)),
+                CliOptionToken::Tsv => opts.output_mode = Some(OutputMode::Table(TableFormat::TSV)),
+                CliOptionToken::Raw => opts.output_mode = Some(OutputMode::Raw),
+                CliOptionToken::Explain => opts.output_mode = Some(OutputMode::Explain),
+                CliOptionToken::Diagram => opts.output_mode = Some(OutputMode::Diagram),
+                CliOptionToken::Layout(layout) => opts.layout = Some(layout),
+                CliOptionToken::Format(template) => {
+                    opts.output_mode = Some(OutputMode::Template(template))
+                }
+                CliOptionToken::Color(color) => opts.color = Some(color),
+                CliOptionToken::From(inconv) => opts.inconv = Some(inconv),
+                CliOptionToken::To(outconvs) => opts
+                    .outconvs
+                    .get_or_insert_with(IndexSet::new)
+                    .extend(outconvs),
+                CliOptionToken::Seed(seed) => opts.seed = Some(seed),
+                CliOptionToken::Svd(path) => opts.svd = Some(path),
+                CliOptionToken::Width(width) => opts.width = Some(width),
+                CliOptionToken::Count(count) => opts.count = Some(count),
+                CliOptionToken::Operation(op) => opts.ops.push(op),
+                // Everything after -- is positional
+                CliOptionToken::EndOfOptions => {
+                    args.extend(input.iter().map(String::as_str));
+                    return Ok((&[], (args, opts)));
+                }
+            }
         }
     }
 
-    Ok((input, opts))
+    Ok((input, (args, opts)))
 }
 
-/// Parse CLI Option
-fn parse_cli_opt(input: &[String]) -> IResult<&[String], CliOptionToken> {
-    parse_cli_opt_help(input)
+/// Parse CLI option, or group of combined short options
+///
+/// Fails with Err::Error if the token is not an option, Err::Failure if it is an invalid one
+fn parse_cli_opt<'a>(
+    input: &'a [String],
+    aliases: &Aliases,
+) -> IResult<&'a [String], Vec<CliOptionToken>, ArgParseError<'a>> {
+    let (rem, token) = any(input).map_err(|_| Err::Error(ArgParseError::Eof))?;
+
+    // End of options
+    if token == "--" {
+        return Ok((rem, vec![CliOptionToken::EndOfOptions]));
+    }
+
+    // Long option, with value either inline as --name=value or as the next token
+    if token.starts_with("--") {
+        let (name, value) = match token.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (token.as_str(), None),
+        };
+        return parse_cli_opt_named(name, name, value, rem, aliases)
+            .map(|(rem, opt)| (rem, vec![opt]))
+            .map_err(|e| match e {
+                Err::Error(ArgParseError::InvalidCliOption(_)) => {
+                    Err::Failure(ArgParseError::InvalidCliOption(token))
+                }
+                e => e,
+            });
+    }
+
+    // Short options, possibly combined as in -rh, where only the last can take a value
+    // Tokens not starting with a known option (ex: -42 or -) are not options
+    let shorts: Vec<char> = match token.strip_prefix('-') {
+        Some(shorts) if !shorts.is_empty() => shorts.chars().collect(),
+        _ => return Err(Err::Error(ArgParseError::InvalidCliOption(token))),
+    };
+    let mut rem = rem;
+    let mut opt_tokens = vec![];
+    for (i, c) in shorts.iter().enumerate() {
+        let last = i + 1 == shorts.len();
+        let (next, opt) = parse_cli_opt_named(
+            &format!("-{}", c),
+            token,
+            None,
+            if last { rem } else { &[] },
+            aliases,
+        )
+        .map_err(|e| match e {
+            Err::Error(e) if i > 0 => Err::Failure(e),
+            e => e,
+        })?;
+        if last {
+            rem = next;
+        }
+        opt_tokens.push(opt);
+    }
+    Ok((rem, opt_tokens))
 }
 
-fn parse_cli_opt_help(input: &[String]) -> IResult<&[String], CliOptionToken> {
-    // map(tag_token("-h"), |_| CliOptionToken::Help)(input)
-    parse_fromstr(input)
+/// Parse a single CLI option given its name
+///
+/// Token is the whole command line token, used in errors
+fn parse_cli_opt_named<'a>(
+    name: &str,
+    token: &'a str,
+    value: Option<&'a str>,
+    input: &'a [String],
+    aliases: &Aliases,
+) -> IResult<&'a [String], CliOptionToken, ArgParseError<'a>> {
+    // Options without a value
+    if let Ok(opt) = name.parse::<CliOptionToken>() {
+        return match value {
+            Some(value) => Err(Err::Failure(ArgParseError::InvalidCliOptionValue(
+                token, value,
+            ))),
+            None => Ok((input, opt)),
+        };
+    }
+
+    // Options followed by a value
+    match name {
+        "--layout" => {
+            let (rem, layout) = parse_cli_opt_value(token, value, input)?;
+            Ok((rem, CliOptionToken::Layout(layout)))
+        }
+        "--format" => {
+            let (rem, s) = parse_cli_opt_str(token, value, input)?;
+            let template = Template::parse_with(s, aliases)
+                .map_err(|_| Err::Failure(ArgParseError::InvalidCliOptionValue(token, s)))?;
+            Ok((rem, CliOptionToken::Format(template)))
+        }
+        "--color" => {
+            let (rem, color) = parse_cli_opt_value(token, value, input)?;
+            Ok((rem, CliOptionToken::Color(color)))
+        }
+        "-i" | "--from" => {
+            let (rem, s) = parse_cli_opt_str(token, value, input)?;
+            let inconv = aliases
+                .parse(s)
+                .map_err(|_| Err::Failure(ArgParseError::InvalidCliOptionValue(token, s)))?;
+            Ok((rem, CliOptionToken::From(inconv)))
+        }
+        "-o" | "--to" => {
+            let (rem, list) = parse_cli_opt_str(token, value, input)?;
+            let outconvs = list
+                .split(',')
+                .map(|s| {
+                    aliases.parse(s).map_err(|_| {
+                        Err::Failure(ArgParseError::UnknownOutputConverter(
+                            s,
+                            suggest_outconv(s, aliases),
+                        ))
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            Ok((rem, CliOptionToken::To(outconvs)))
+        }
+        "--svd" => {
+            let (rem, path) = parse_cli_opt_str(token, value, input)?;
+            Ok((rem, CliOptionToken::Svd(PathBuf::from(path))))
+        }
+        "--seed" => {
+            let (rem, seed) = parse_cli_opt_value(token, value, input)?;
+            Ok((rem, CliOptionToken::Seed(seed)))
+        }
+        "--width" => {
+            let (rem, s) = parse_cli_opt_str(token, value, input)?;
+            match s.parse() {
+                Ok(width @ 1..=128) => Ok((rem, CliOptionToken::Width(width))),
+                _ => Err(Err::Failure(ArgParseError::InvalidCliOptionValue(token, s))),
+            }
+        }
+        "--rotl" | "--rotr" => {
+            let (rem, s) = parse_cli_opt_str(token, value, input)?;
+            match (name, s.parse()) {
+                ("--rotl", Ok(n)) => {
+                    Ok((rem, CliOptionToken::Operation(BitOperation::RotateLeft(n))))
+                }
+                (_, Ok(n)) => Ok((rem, CliOptionToken::Operation(BitOperation::RotateRight(n)))),
+                _ => Err(Err::Failure(ArgParseError::InvalidCliOptionValue(token, s))),
+            }
+        }
+        "--sext" | "--zext" | "--trunc" => {
+            let (rem, s) = parse_cli_opt_str(token, value, input)?;
+            let width = match s.parse() {
+                Ok(width @ 1..=128) => width,
+                _ => return Err(Err::Failure(ArgParseError::InvalidCliOptionValue(token, s))),
+            };
+            let op = match name {
+                "--sext" => BitOperation::SignExtend(width),
+                "--zext" => BitOperation::ZeroExtend(width),
+                _ => BitOperation::Truncate(width),
+            };
+            Ok((rem, CliOptionToken::Operation(op)))
+        }
+        "--count" => {
+            let (rem, s) = parse_cli_opt_str(token, value, input)?;
+            match s.parse() {
+                Ok(count @ 1..) => Ok((rem, CliOptionToken::Count(count))),
+                _ => Err(Err::Failure(ArgParseError::InvalidCliOptionValue(token, s))),
+            }
+        }
+        _ => Err(Err::Error(ArgParseError::InvalidCliOption(token))),
+    }
+}
+
+/// Parse the value of a CLI option using FromStr
+fn parse_cli_opt_value<'a, T: FromStr>(
+    name: &'a str,
+    value: Option<&'a str>,
+    input: &'a [String],
+) -> IResult<&'a [String], T, ArgParseError<'a>> {
+    let (input, s) = parse_cli_opt_str(name, value, input)?;
+    let val = s
+        .parse()
+        .map_err(|_| Err::Failure(ArgParseError::InvalidCliOptionValue(name, s)))?;
+    Ok((input, val))
+}
+
+/// Get the value of a CLI option as a string
+///
+/// The value is taken from the next token, unless already given inline
+fn parse_cli_opt_str<'a>(
+    name: &'a str,
+    value: Option<&'a str>,
+    input: &'a [String],
+) -> IResult<&'a [String], &'a str, ArgParseError<'a>> {
+    match value {
+        Some(s) => Ok((input, s)),
+        None => any(input)
+            .map(|(input, s)| (input, s.as_str()))
+            .map_err(|_| Err::Failure(ArgParseError::MissingCliOptionValue(name))),
+    }
+}
+
+/// Parse the input converter named by the first token, if any, before the value
+///
+/// A name not followed by a valid value for its converter is the value itself, as in `dec bin`
+fn parse_positional_inconv<'b, 'a>(
+    input: &'b [&'a str],
+    aliases: &Aliases,
+) -> (&'b [&'a str], Option<InputConverterType>) {
+    match parse_conv_type::<InputConverterType>(input, aliases) {
+        Ok((rem, inconv)) => {
+            let valid = rem
+                .first()
+                .is_some_and(|value| inconv.get_converter().convert(value).is_ok());
+            if valid || parse_outconvs_list(rem, aliases).is_err() {
+                (rem, Some(inconv))
+            } else {
+                (input, None)
+            }
+        }
+        Err(_) => (input, None),
+    }
 }
 
 /// Parse output converter type
-fn parse_outconv_type(input: &[String]) -> IResult<&[String], OutputConverterType, ArgParseError> {
-    let (input, s) = any(input).map_err(|_| Err::Error(ArgParseError::Eof))?;
-    let (_, val) = parse_fromstr(&[s.clone()])
-        .map_err(|_| Err::Error(ArgParseError::UnknownOutputConverter(s)))?;
+fn parse_outconv_type<'b, 'a>(
+    input: &'b [&'a str],
+    aliases: &Aliases,
+) -> IResult<&'b [&'a str], OutputConverterType, ArgParseError<'a>> {
+    let (input, &s) = any(input).map_err(|_| Err::Error(ArgParseError::Eof))?;
+
+    // Register converter, followed by the register name
+    if s.eq_ignore_ascii_case(REGISTER_CONVERTER) {
+        let (input, name) = parse_register_name(input)?;
+        return Ok((input, OutputConverterType::REG(name)));
+    }
+
+    // Flags converter, followed by the flag set name
+    if s.eq_ignore_ascii_case(FLAGS_CONVERTER) {
+        let (input, name) = parse_flag_set_name(input)?;
+        return Ok((input, OutputConverterType::FLAGS(name)));
+    }
+
+    // Enum converter, followed by the enumeration name
+    if s.eq_ignore_ascii_case(ENUM_CONVERTER) {
+        let (input, name) = parse_enum_name(input)?;
+        return Ok((input, OutputConverterType::ENUM(name)));
+    }
+
+    let (_, val) = parse_conv_type(&[s], aliases).map_err(|_| {
+        Err::Error(ArgParseError::UnknownOutputConverter(
+            s,
+            suggest_outconv(s, aliases),
+        ))
+    })?;
     Ok((input, val))
 }
 
+/// Parse the name of a register declared in the config file or in the SVD file
+fn parse_register_name<'b, 'a>(
+    input: &'b [&'a str],
+) -> IResult<&'b [&'a str], &'static str, ArgParseError<'a>> {
+    let (input, &name) =
+        any(input).map_err(|_| Err::Failure(ArgParseError::MissingRegisterName))?;
+    match registers().get(name) {
+        Some((name, _)) => Ok((input, name)),
+        None => Err(Err::Failure(ArgParseError::UnknownRegister(
+            name,
+            closest_name(name, registers().names().map(str::to_string)),
+        ))),
+    }
+}
+
+/// Parse the name of a built-in flag set or one declared in the config file
+fn parse_flag_set_name<'b, 'a>(
+    input: &'b [&'a str],
+) -> IResult<&'b [&'a str], &'static str, ArgParseError<'a>> {
+    let (input, &name) = any(input).map_err(|_| Err::Failure(ArgParseError::MissingFlagSetName))?;
+    match flag_sets().get(name) {
+        Some((name, _)) => Ok((input, name)),
+        None => Err(Err::Failure(ArgParseError::UnknownFlagSet(
+            name,
+            closest_name(name, flag_sets().names().map(str::to_string)),
+        ))),
+    }
+}
+
+/// Parse the name of an enumeration declared in the config file
+fn parse_enum_name<'b, 'a>(
+    input: &'b [&'a str],
+) -> IResult<&'b [&'a str], &'static str, ArgParseError<'a>> {
+    let (input, &name) = any(input).map_err(|_| Err::Failure(ArgParseError::MissingEnumName))?;
+    match enums().get(name) {
+        Some((name, _)) => Ok((input, name)),
+        None => Err(Err::Failure(ArgParseError::UnknownEnum(
+            name,
+            closest_name(name, enums().names().map(str::to_string)),
+        ))),
+    }
+}
+
+/// Find the known output converter name closest to an unknown one
+fn suggest_outconv(name: &str, aliases: &Aliases) -> Option<String> {
+    closest_name(
+        name,
+        OutputConverterType::iter()
+            .flat_map(|outconv| outconv.names().iter().map(|s| s.to_ascii_lowercase()))
+            .chain(aliases.names::<OutputConverterType>().map(str::to_string)),
+    )
+}
+
+/// Find the candidate closest to a name, ignoring case, if close enough to be a typo
+fn closest_name(name: &str, candidates: impl Iterator<Item = String>) -> Option<String> {
+    let name = name.to_ascii_lowercase();
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .map(|candidate| {
+            let distance = edit_distance(&name, &candidate.to_ascii_lowercase());
+            (distance, candidate)
+        })
+        .filter(|(distance, _)| *distance <= max_distance && *distance < name.chars().count())
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Compute the Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            cur.push(substitution.min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+
+    prev[b.len()]
+}
+
 /// Parse output converters list
-fn parse_outconvs_list(
-    mut input: &[String],
-) -> IResult<&[String], Option<IndexSet<OutputConverterType>>, ArgParseError> {
+fn parse_outconvs_list<'b, 'a>(
+    mut input: &'b [&'a str],
+    aliases: &Aliases,
+) -> IResult<&'b [&'a str], Option<IndexSet<OutputConverterType>>, ArgParseError<'a>> {
     let mut outconvs = IndexSet::new();
 
-    while input.len() > 0 {
+    while !input.is_empty() {
         // Parse token
-        match parse_outconv_type(input) {
+        match parse_outconv_type(input, aliases) {
             Ok((rem, val)) => {
                 // Add value to output vector
                 outconvs.insert(val);
@@ -193,7 +782,7 @@ fn parse_outconvs_list(
     }
 
     // Map output converters to None if none were entered
-    let outconvs = if outconvs.len() > 0 {
+    let outconvs = if !outconvs.is_empty() {
         Some(outconvs)
     } else {
         None
@@ -202,10 +791,13 @@ fn parse_outconvs_list(
     Ok((input, outconvs))
 }
 
-/// Parse first token using FromStr
-fn parse_fromstr<T: FromStr>(input: &[String]) -> IResult<&[String], T> {
+/// Parse first token as a converter name, resolving user aliases
+fn parse_conv_type<'b, 'a, T: FromStr>(
+    input: &'b [&'a str],
+    aliases: &Aliases,
+) -> IResult<&'b [&'a str], T> {
     // Check that there is a token to parse
-    if input.len() < 1 {
+    if input.is_empty() {
         return Err(Err::Error(ParseError::from_error_kind(
             input,
             ErrorKind::Eof,
@@ -213,7 +805,7 @@ fn parse_fromstr<T: FromStr>(input: &[String]) -> IResult<&[String], T> {
     }
 
     // Parse token
-    let val = match input[0].parse::<T>() {
+    let val = match aliases.parse::<T>(input[0]) {
         Ok(val) => val,
         Err(_) => {
             return Err(Err::Error(ParseError::from_error_kind(
@@ -227,10 +819,10 @@ fn parse_fromstr<T: FromStr>(input: &[String]) -> IResult<&[String], T> {
     Ok((&input[1..], val))
 }
 
-/// Consume any token as string
-fn any(input: &[String]) -> IResult<&[String], &String> {
+/// Consume any token
+fn any<T>(input: &[T]) -> IResult<&[T], &T> {
     // Check that there is a token to parse
-    if input.len() < 1 {
+    if input.is_empty() {
         return Err(Err::Error(Error::from_error_kind(input, ErrorKind::Eof)));
     }
 
@@ -242,36 +834,37 @@ mod tests {
     use indexmap::indexset;
 
     use super::*;
+    use crate::convert::{set_enums, set_registers};
+    use std::sync::Once;
 
     #[test]
-    fn parse_fromstr_inconv_ok() {
+    fn parse_conv_type_inconv_ok() {
         let tests = [
             (
-                vec!["HEX".to_string(), "ciaone".to_string(), "".to_string()],
-                vec!["ciaone".to_string(), "".to_string()],
+                vec!["HEX", "ciaone", ""],
+                vec!["ciaone", ""],
                 InputConverterType::HEX,
             ),
-            (vec!["bin".to_string()], vec![], InputConverterType::BIN),
-            (
-                vec!["DEC".to_string(), "".to_string()],
-                vec!["".to_string()],
-                InputConverterType::DEC,
-            ),
+            (vec!["bin"], vec![], InputConverterType::BIN),
+            (vec!["DEC", ""], vec![""], InputConverterType::DEC),
+            (vec!["x"], vec![], InputConverterType::HEX),
+            (vec!["Bn"], vec![], InputConverterType::BIN),
         ];
+        let aliases = Aliases::from([("bn", "bin")]);
 
         for (input, exprem, exp) in tests {
-            let (rem, out) = parse_fromstr::<InputConverterType>(&input).unwrap();
+            let (rem, out) = parse_conv_type::<InputConverterType>(&input, &aliases).unwrap();
             assert_eq!(rem, exprem);
             assert_eq!(out, exp);
         }
     }
 
     #[test]
-    fn parse_fromstr_inconv_err() {
-        let tests = [vec![], vec!["hello".to_string(), "".to_string()]];
+    fn parse_conv_type_inconv_err() {
+        let tests: [Vec<&str>; 2] = [vec![], vec!["hello", ""]];
 
         for input in tests {
-            parse_fromstr::<InputConverterType>(&input).unwrap_err();
+            parse_conv_type::<InputConverterType>(&input, &Aliases::default()).unwrap_err();
         }
     }
 
@@ -299,7 +892,7 @@ mod tests {
 
     #[test]
     fn parse_any_err() {
-        let tests = [vec![]];
+        let tests: [Vec<String>; 1] = [vec![]];
 
         for input in tests {
             any(&input).unwrap_err();
@@ -315,6 +908,10 @@ mod tests {
                 CliOptions {
                     help: false,
                     version: false,
+                    output_mode: None,
+                    layout: None,
+                    color: None,
+                    ..CliOptions::new()
                 },
             ),
             (
@@ -323,6 +920,10 @@ mod tests {
                 CliOptions {
                     help: false,
                     version: false,
+                    output_mode: None,
+                    layout: None,
+                    color: None,
+                    ..CliOptions::new()
                 },
             ),
             (
@@ -331,13 +932,124 @@ mod tests {
                 CliOptions {
                     help: true,
                     version: false,
+                    output_mode: None,
+                    layout: None,
+                    color: None,
+                    ..CliOptions::new()
                 },
             ),
+            (
+                vec!["--tsv".to_string(), "test".to_string()],
+                vec!["test".to_string()],
+                CliOptions {
+                    help: false,
+                    version: false,
+                    output_mode: Some(OutputMode::Table(TableFormat::TSV)),
+                    layout: None,
+                    color: None,
+                    ..CliOptions::new()
+                },
+            ),
+            (
+                vec![
+                    "--color=never".to_string(),
+                    "--layout".to_string(),
+                    "grid".to_string(),
+                    "test".to_string(),
+                ],
+                vec!["test".to_string()],
+                CliOptions {
+                    help: false,
+                    version: false,
+                    output_mode: None,
+                    layout: Some(Layout::Grid),
+                    color: Some(ColorMode::Never),
+                    ..CliOptions::new()
+                },
+            ),
+            (
+                vec!["-rv".to_string(), "test".to_string()],
+                vec!["test".to_string()],
+                CliOptions {
+                    version: true,
+                    output_mode: Some(OutputMode::Raw),
+                    ..CliOptions::new()
+                },
+            ),
+            (
+                vec!["-ri".to_string(), "hex".to_string(), "10".to_string()],
+                vec!["10".to_string()],
+                CliOptions {
+                    output_mode: Some(OutputMode::Raw),
+                    inconv: Some(InputConverterType::HEX),
+                    ..CliOptions::new()
+                },
+            ),
+            (
+                vec![
+                    "--from".to_string(),
+                    "hex".to_string(),
+                    "--to=bin,dec".to_string(),
+                    "-o".to_string(),
+                    "hex".to_string(),
+                    "--".to_string(),
+                    "-h".to_string(),
+                ],
+                vec!["-h".to_string()],
+                CliOptions {
+                    inconv: Some(InputConverterType::HEX),
+                    outconvs: Some(indexset! {
+                        OutputConverterType::BIN,
+                        OutputConverterType::DEC,
+                        OutputConverterType::HEX
+                    }),
+                    ..CliOptions::new()
+                },
+            ),
+            (
+                vec![
+                    "--diagram".to_string(),
+                    "--width=16".to_string(),
+                    "--svd".to_string(),
+                    "chip.svd".to_string(),
+                    "test".to_string(),
+                ],
+                vec!["test".to_string()],
+                CliOptions {
+                    output_mode: Some(OutputMode::Diagram),
+                    width: Some(16),
+                    svd: Some(PathBuf::from("chip.svd")),
+                    ..CliOptions::new()
+                },
+            ),
+            (
+                vec![
+                    "--sext".to_string(),
+                    "8".to_string(),
+                    "--rotl=4".to_string(),
+                    "--bswap".to_string(),
+                    "test".to_string(),
+                ],
+                vec!["test".to_string()],
+                CliOptions {
+                    ops: vec![
+                        BitOperation::SignExtend(8),
+                        BitOperation::RotateLeft(4),
+                        BitOperation::ByteSwap,
+                    ],
+                    ..CliOptions::new()
+                },
+            ),
+            (
+                vec!["-42".to_string()],
+                vec!["-42".to_string()],
+                CliOptions::new(),
+            ),
         ];
 
         for (input, exprem, exp) in tests {
-            let (rem, out) = parse_cli_options(&input).unwrap();
-            assert_eq!(rem, exprem);
+            let (_, (args, out)) = parse_cli_options(&input, &Aliases::default()).unwrap();
+            assert_eq!(args, exprem);
             assert_eq!(out, exp);
         }
     }
@@ -351,6 +1063,12 @@ mod tests {
                     input: "test1".to_string(),
                     inconv: None,
                     outconvs: None,
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
                 },
             ),
             (
@@ -359,6 +1077,12 @@ mod tests {
                     input: "test2".to_string(),
                     inconv: Some(InputConverterType::HEX),
                     outconvs: None,
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
                 },
             ),
             (
@@ -367,6 +1091,12 @@ mod tests {
                     input: "test3".to_string(),
                     inconv: None,
                     outconvs: Some(indexset! {OutputConverterType::HEX, OutputConverterType::DEC}),
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
                 },
             ),
             (
@@ -380,30 +1110,516 @@ mod tests {
                     input: "test4".to_string(),
                     inconv: Some(InputConverterType::BIN),
                     outconvs: Some(indexset! {OutputConverterType::HEX, OutputConverterType::BIN}),
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec!["--csv".to_string(), "-".to_string(), "bin".to_string()],
+                ArgVals {
+                    input: "-".to_string(),
+                    inconv: None,
+                    outconvs: Some(indexset! {OutputConverterType::BIN}),
+                    output_mode: Some(OutputMode::Table(TableFormat::CSV)),
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec![
+                    "-r".to_string(),
+                    "dec".to_string(),
+                    "255".to_string(),
+                    "hex".to_string(),
+                ],
+                ArgVals {
+                    input: "255".to_string(),
+                    inconv: Some(InputConverterType::DEC),
+                    outconvs: Some(indexset! {OutputConverterType::HEX}),
+                    output_mode: Some(OutputMode::Raw),
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec!["--layout".to_string(), "grid".to_string(), "42".to_string()],
+                ArgVals {
+                    input: "42".to_string(),
+                    inconv: None,
+                    outconvs: None,
+                    output_mode: None,
+                    layout: Some(Layout::Grid),
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec![
+                    "--format".to_string(),
+                    "0x{hex:04}".to_string(),
+                    "dec".to_string(),
+                    "42".to_string(),
+                ],
+                ArgVals {
+                    input: "42".to_string(),
+                    inconv: Some(InputConverterType::DEC),
+                    outconvs: None,
+                    output_mode: Some(OutputMode::Template("0x{hex:04}".parse().unwrap())),
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec!["dec".to_string()],
+                ArgVals {
+                    input: "dec".to_string(),
+                    inconv: None,
+                    outconvs: None,
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec!["-r".to_string(), "bin".to_string()],
+                ArgVals {
+                    input: "bin".to_string(),
+                    inconv: None,
+                    outconvs: None,
+                    output_mode: Some(OutputMode::Raw),
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec!["=hex".to_string(), "dec".to_string()],
+                ArgVals {
+                    input: "=hex".to_string(),
+                    inconv: None,
+                    outconvs: Some(indexset! {OutputConverterType::DEC}),
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec!["-i".to_string(), "hex".to_string(), "dec".to_string()],
+                ArgVals {
+                    input: "dec".to_string(),
+                    inconv: Some(InputConverterType::HEX),
+                    outconvs: None,
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec![
+                    "--to".to_string(),
+                    "bin".to_string(),
+                    "--".to_string(),
+                    "-42".to_string(),
+                    "hex".to_string(),
+                ],
+                ArgVals {
+                    input: "-42".to_string(),
+                    inconv: None,
+                    outconvs: Some(indexset! {OutputConverterType::BIN, OutputConverterType::HEX}),
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec![
+                    "-i".to_string(),
+                    "hx".to_string(),
+                    "ff".to_string(),
+                    "d".to_string(),
+                    "bn".to_string(),
+                ],
+                ArgVals {
+                    input: "ff".to_string(),
+                    inconv: Some(InputConverterType::HEX),
+                    outconvs: Some(indexset! {OutputConverterType::DEC, OutputConverterType::BIN}),
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec![
+                    "--width".to_string(),
+                    "12".to_string(),
+                    "42".to_string(),
+                    "bits".to_string(),
+                ],
+                ArgVals {
+                    input: "42".to_string(),
+                    inconv: None,
+                    outconvs: Some(indexset! {OutputConverterType::BITS}),
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: Some(12),
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec!["-ar".to_string(), "0b11".to_string()],
+                ArgVals {
+                    input: "0b11".to_string(),
+                    inconv: None,
+                    outconvs: None,
+                    output_mode: Some(OutputMode::Raw),
+                    layout: None,
+                    show_all: true,
+                    width: None,
+                    ops: vec![],
+                    quiz: None,
+                },
+            ),
+            (
+                vec![
+                    "--to=hex".to_string(),
+                    "quiz".to_string(),
+                    "--seed".to_string(),
+                    "42".to_string(),
+                    "-i".to_string(),
+                    "dec".to_string(),
+                    "--width=16".to_string(),
+                ],
+                ArgVals {
+                    input: String::new(),
+                    inconv: Some(InputConverterType::DEC),
+                    outconvs: Some(indexset! {OutputConverterType::HEX}),
+                    output_mode: None,
+                    layout: None,
+                    show_all: false,
+                    width: Some(16),
+                    ops: vec![],
+                    quiz: Some(QuizSettings {
+                        seed: Some(42),
+                        width: 16,
+                        count: 10,
+                    }),
                 },
             ),
         ];
+        let aliases = Aliases::from([("hx", "hex"), ("bn", "bin")]);
 
         for (input, exp) in tests {
-            let (_, out) = parse_arguments(&input).unwrap();
+            let (_, out) = parse_arguments(&input, &aliases).unwrap();
             assert_eq!(out, exp);
         }
     }
 
+    /// Declare the registers and enumerations used in tests, once before any of them is looked up
+    fn set_test_definitions() {
+        static DEFINITIONS: Once = Once::new();
+        DEFINITIONS.call_once(|| {
+            let registers = toml::from_str("[UART_CR]\nEN = 0\n[SPI_CR]\nEN = 0").unwrap();
+            set_registers(registers).unwrap();
+            set_enums(toml::from_str("[opcode]\n0x01 = \"NOP\"").unwrap()).unwrap();
+        });
+    }
+
+    #[test]
+    fn parse_outconv_type_register() {
+        set_test_definitions();
+
+        let input = vec!["reg", "uart_cr", "hex"];
+        let (rem, out) = parse_outconv_type(&input, &Aliases::default()).unwrap();
+        assert_eq!(rem, vec!["hex"]);
+        assert_eq!(out, OutputConverterType::REG("UART_CR"));
+
+        let tests = [
+            (
+                vec!["REG", "UART_CF"],
+                ArgParseError::UnknownRegister("UART_CF", Some("UART_CR".to_string())),
+            ),
+            (
+                vec!["reg", "I2C"],
+                ArgParseError::UnknownRegister("I2C", None),
+            ),
+            (vec!["reg"], ArgParseError::MissingRegisterName),
+        ];
+        for (input, experr) in tests {
+            match parse_outconv_type(&input, &Aliases::default()).unwrap_err() {
+                Err::Failure(e) => assert_eq!(e, experr),
+                _ => panic!(),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_arguments_trailing_options() {
+        let input = vec![
+            "--reverse".to_string(),
+            "hex".to_string(),
+            "0x80".to_string(),
+            "--sext".to_string(),
+            "8".to_string(),
+            "dec".to_string(),
+        ];
+        let (_, out) = parse_arguments(&input, &Aliases::default()).unwrap();
+        assert_eq!(out.input, "0x80");
+        assert_eq!(out.outconvs, Some(indexset! {OutputConverterType::DEC}));
+        assert_eq!(
+            out.ops,
+            vec![BitOperation::Reverse, BitOperation::SignExtend(8)]
+        );
+
+        let input = vec![
+            "0x12".to_string(),
+            "--rotl".to_string(),
+            "4".to_string(),
+            "--width".to_string(),
+            "8".to_string(),
+            "hex".to_string(),
+            "--raw".to_string(),
+        ];
+        let (_, out) = parse_arguments(&input, &Aliases::default()).unwrap();
+        assert_eq!(out.input, "0x12");
+        assert_eq!(out.width, Some(8));
+        assert_eq!(out.output_mode, Some(OutputMode::Raw));
+
+        let input = vec!["255".to_string(), "--to".to_string(), "hex".to_string()];
+        let (_, out) = parse_arguments(&input, &Aliases::default()).unwrap();
+        assert_eq!(out.input, "255");
+        assert_eq!(out.inconv, None);
+        assert_eq!(out.outconvs, Some(indexset! {OutputConverterType::HEX}));
+
+        // Nothing after -- is an option
+        let input = vec!["--".to_string(), "1".to_string(), "--raw".to_string()];
+        let err = parse_arguments(&input, &Aliases::default()).unwrap_err();
+        assert_eq!(
+            err,
+            Err::Error(ArgParseError::UnknownOutputConverter("--raw", None))
+        );
+    }
+
+    #[test]
+    fn parse_arguments_mask() {
+        let input = vec!["mask".to_string(), "15:8".to_string(), "hex".to_string()];
+        let (_, out) = parse_arguments(&input, &Aliases::default()).unwrap();
+        assert_eq!(out.input, "15:8");
+        assert_eq!(out.inconv, Some(InputConverterType::MASK));
+        assert_eq!(out.outconvs, Some(indexset! {OutputConverterType::HEX}));
+    }
+
+    #[test]
+    fn parse_arguments_value_naming_inconv() {
+        let tests = [
+            (vec!["d", "bin"], "d", None),
+            (vec!["b", "hex"], "b", None),
+            (vec!["dec", "bin"], "dec", None),
+            (vec!["hex", "dec"], "dec", Some(InputConverterType::HEX)),
+            (vec!["hex", "zz"], "zz", Some(InputConverterType::HEX)),
+            (vec!["x", "ff", "dec"], "ff", Some(InputConverterType::HEX)),
+            (vec!["d", "10", "hex"], "10", Some(InputConverterType::DEC)),
+        ];
+        for (input, exp_input, exp_inconv) in tests {
+            let input: Vec<String> = input.into_iter().map(str::to_string).collect();
+            let (_, out) = parse_arguments(&input, &Aliases::default()).unwrap();
+            assert_eq!(out.input, exp_input);
+            assert_eq!(out.inconv, exp_inconv);
+        }
+    }
+
+    #[test]
+    fn parse_arguments_register() {
+        set_test_definitions();
+
+        let input = vec![
+            "reg".to_string(),
+            "spi_cr".to_string(),
+            "EN=1".to_string(),
+            "hex".to_string(),
+        ];
+        let (_, out) = parse_arguments(&input, &Aliases::default()).unwrap();
+        assert_eq!(out.input, "EN=1");
+        assert_eq!(out.inconv, Some(InputConverterType::REG("SPI_CR")));
+        assert_eq!(out.outconvs, Some(indexset! {OutputConverterType::HEX}));
+    }
+
+    #[test]
+    fn parse_outconv_type_enum() {
+        set_test_definitions();
+
+        let input = vec!["enum", "OPCODE"];
+        let (rem, out) = parse_outconv_type(&input, &Aliases::default()).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(out, OutputConverterType::ENUM("opcode"));
+
+        let tests = [
+            (
+                vec!["enum", "opcod"],
+                ArgParseError::UnknownEnum("opcod", Some("opcode".to_string())),
+            ),
+            (vec!["ENUM"], ArgParseError::MissingEnumName),
+        ];
+        for (input, experr) in tests {
+            match parse_outconv_type(&input, &Aliases::default()).unwrap_err() {
+                Err::Failure(e) => assert_eq!(e, experr),
+                _ => panic!(),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_outconv_type_flags() {
+        let input = vec!["FLAGS", "Open"];
+        let (rem, out) = parse_outconv_type(&input, &Aliases::default()).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(out, OutputConverterType::FLAGS("open"));
+
+        let tests = [
+            (
+                vec!["flags", "prt"],
+                ArgParseError::UnknownFlagSet("prt", Some("prot".to_string())),
+            ),
+            (vec!["flags"], ArgParseError::MissingFlagSetName),
+        ];
+        for (input, experr) in tests {
+            match parse_outconv_type(&input, &Aliases::default()).unwrap_err() {
+                Err::Failure(e) => assert_eq!(e, experr),
+                _ => panic!(),
+            }
+        }
+
+        let input = vec![
+            "flags".to_string(),
+            "open".to_string(),
+            "O_CREAT|O_RDWR".to_string(),
+        ];
+        let (_, out) = parse_arguments(&input, &Aliases::default()).unwrap();
+        assert_eq!(out.inconv, Some(InputConverterType::FLAGS("open")));
+    }
+
+    #[test]
+    fn edit_distance_ok() {
+        let tests = [
+            ("", "", 0),
+            ("dec", "dec", 0),
+            ("dcc", "dec", 1),
+            ("hx", "hex", 1),
+            ("", "bin", 3),
+            ("kitten", "sitting", 3),
+        ];
+        for (a, b, exp) in tests {
+            assert_eq!(edit_distance(a, b), exp);
+        }
+    }
+
     #[test]
     fn parse_arguments_err() {
         let tests = [
             (vec![], ArgParseError::MissingInput),
             (
                 vec!["test".to_string(), "test2".to_string()],
-                ArgParseError::UnknownOutputConverter("test2"),
+                ArgParseError::UnknownOutputConverter("test2", None),
             ),
             (vec!["-h".to_string()], ArgParseError::GracefulExit),
             (vec!["-v".to_string()], ArgParseError::GracefulExit),
+            (
+                vec!["--format".to_string(), "{hx}".to_string(), "1".to_string()],
+                ArgParseError::InvalidCliOptionValue("--format", "{hx}"),
+            ),
+            (
+                vec!["--layout".to_string()],
+                ArgParseError::MissingCliOptionValue("--layout"),
+            ),
+            (
+                vec!["--bogus".to_string(), "1".to_string()],
+                ArgParseError::InvalidCliOption("--bogus"),
+            ),
+            (
+                vec!["-rx".to_string(), "1".to_string()],
+                ArgParseError::InvalidCliOption("-rx"),
+            ),
+            (
+                vec!["--raw=yes".to_string(), "1".to_string()],
+                ArgParseError::InvalidCliOptionValue("--raw", "yes"),
+            ),
+            (
+                vec!["--to".to_string(), "bin,dcc".to_string(), "1".to_string()],
+                ArgParseError::UnknownOutputConverter("dcc", Some("dec".to_string())),
+            ),
+            (
+                vec!["--layout".to_string(), "wide".to_string(), "42".to_string()],
+                ArgParseError::InvalidCliOptionValue("--layout", "wide"),
+            ),
+            (
+                vec!["1".to_string(), "hexadecmal".to_string()],
+                ArgParseError::UnknownOutputConverter(
+                    "hexadecmal",
+                    Some("hexadecimal".to_string()),
+                ),
+            ),
+            (
+                vec!["1".to_string(), "bitz".to_string()],
+                ArgParseError::UnknownOutputConverter("bitz", Some("bits".to_string())),
+            ),
+            (
+                vec!["--seed".to_string(), "1".to_string(), "42".to_string()],
+                ArgParseError::QuizOptionWithoutQuiz,
+            ),
+            (
+                vec!["quiz".to_string(), "42".to_string()],
+                ArgParseError::UnexpectedArgument("42"),
+            ),
+            (
+                vec!["quiz".to_string(), "--width=0".to_string()],
+                ArgParseError::InvalidCliOptionValue("--width", "0"),
+            ),
+            (
+                vec!["--sext=129".to_string(), "1".to_string()],
+                ArgParseError::InvalidCliOptionValue("--sext", "129"),
+            ),
+            (
+                vec!["--rotr".to_string(), "-1".to_string(), "1".to_string()],
+                ArgParseError::InvalidCliOptionValue("--rotr", "-1"),
+            ),
         ];
+        let aliases = Aliases::from([("bn", "bin")]);
 
         for (input, experr) in tests {
-            let err = parse_arguments(&input).unwrap_err();
+            let err = parse_arguments(&input, &aliases).unwrap_err();
             match err {
                 Err::Failure(_) | Err::Incomplete(_) => panic!(),
                 Err::Error(e) => assert_eq!(e, experr),
@@ -1,11 +1,21 @@
-use serde::Deserialize;
-use std::{error::Error, fs, path::Path};
+use serde::{de, Deserialize};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use crate::{
-    constants::{default_inconvs, default_outconvs},
-    convert::{InputConverterType, OutputConverterType},
+    constants::{default_color, default_inconvs, default_layout, default_outconvs},
+    convert::{Constants, Enums, FlagSets, InputConverterType, OutputConverterType, Registers},
+    template::Template,
+    ui::{ColorMode, Theme},
 };
 
+use super::Layout;
+
 /// Config file options
 #[derive(Deserialize, Debug)]
 pub struct Config {
@@ -14,6 +24,93 @@ pub struct Config {
 
     #[serde(default = "default_inconvs")]
     pub default_inconvs: Vec<InputConverterType>,
+
+    #[serde(default = "default_layout")]
+    pub layout: Layout,
+
+    #[serde(default)]
+    pub format: Option<Template>,
+
+    #[serde(default = "default_color")]
+    pub color: ColorMode,
+
+    #[serde(default)]
+    pub theme: Theme,
+
+    #[serde(default)]
+    pub aliases: Aliases,
+
+    #[serde(default)]
+    pub registers: Registers,
+
+    #[serde(default)]
+    pub svd: Option<PathBuf>,
+
+    #[serde(default)]
+    pub flags: FlagSets,
+
+    #[serde(default)]
+    pub constants: Constants,
+
+    #[serde(default)]
+    pub enums: Enums,
+}
+
+/// User-defined converter names, mapping each alias to a converter name
+#[derive(Debug, PartialEq, Default)]
+pub struct Aliases(HashMap<String, String>);
+
+impl Aliases {
+    /// Parse converter name, looking up user aliases first
+    pub fn parse<T: FromStr>(&self, name: &str) -> Result<T, T::Err> {
+        match self.0.get(&name.to_ascii_lowercase()) {
+            Some(target) => target.parse(),
+            None => name.parse(),
+        }
+    }
+
+    /// Get all aliases standing for a converter of a given type
+    pub fn names<T: FromStr>(&self) -> impl Iterator<Item = &str> {
+        self.0
+            .iter()
+            .filter(|(_, target)| target.parse::<T>().is_ok())
+            .map(|(alias, _)| alias.as_str())
+    }
+}
+
+impl<const N: usize> From<[(&str, &str); N]> for Aliases {
+    fn from(value: [(&str, &str); N]) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|(alias, target)| (alias.to_ascii_lowercase(), target.to_string()))
+                .collect(),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for Aliases {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let map = HashMap::<String, String>::deserialize(deserializer)?;
+        for (alias, target) in &map {
+            if target.parse::<InputConverterType>().is_err()
+                && target.parse::<OutputConverterType>().is_err()
+            {
+                return Err(de::Error::custom(format!(
+                    "Invalid converter for alias {}: {}",
+                    alias, target
+                )));
+            }
+        }
+        Ok(Self(
+            map.into_iter()
+                .map(|(alias, target)| (alias.to_ascii_lowercase(), target))
+                .collect(),
+        ))
+    }
 }
 
 impl Config {
@@ -28,3 +125,37 @@ impl Config {
         Ok(config)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases_parse_ok() {
+        let aliases: Aliases = toml::from_str(
+            r#"
+            HX = "hex"
+            bn = "b"
+            "#,
+        )
+        .unwrap();
+
+        let tests = [
+            ("hx", OutputConverterType::HEX),
+            ("Bn", OutputConverterType::BIN),
+            ("dec", OutputConverterType::DEC),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(aliases.parse::<OutputConverterType>(input).unwrap(), exp);
+        }
+        aliases.parse::<OutputConverterType>("bnn").unwrap_err();
+    }
+
+    #[test]
+    fn aliases_deserialize_err() {
+        let tests = [r#"hx = "hexa""#, r#"hx = 16"#];
+        for input in tests {
+            toml::from_str::<Aliases>(input).unwrap_err();
+        }
+    }
+}
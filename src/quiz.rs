@@ -0,0 +1,278 @@
+use std::{
+    io::{self, BufRead, Write},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use strum::IntoEnumIterator;
+
+use crate::{
+    convert::{
+        ConversionError, InputConverterType, InputError, IntermediateValue, OutputConverterType,
+    },
+    ui::ColorPalette,
+    Opts,
+};
+
+/// Quiz session settings
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct QuizSettings {
+    /// Seed of the random values, to repeat a session
+    pub seed: Option<u64>,
+    /// Width in bits of the random values
+    pub width: u32,
+    /// Number of questions
+    pub count: usize,
+}
+
+/// Base conversion drill
+pub struct Quiz {
+    rng: Rng,
+    width: u32,
+    pairs: Vec<(InputConverterType, OutputConverterType)>,
+}
+
+/// Value to be converted from a base to another
+#[derive(Debug, PartialEq)]
+pub struct Question {
+    pub value: IntermediateValue,
+    pub from: InputConverterType,
+    pub to: OutputConverterType,
+}
+
+/// Result of a quiz session
+#[derive(Debug, PartialEq)]
+pub struct Score {
+    pub correct: usize,
+    pub asked: usize,
+    pub time: Duration,
+}
+
+impl Quiz {
+    /// Set up quiz over the numeric bases selected in opts
+    ///
+    /// Returns None if there aren't two different bases to convert between
+    pub fn new(seed: u64, width: u32, opts: &Opts) -> Option<Self> {
+        let pairs: Vec<_> = opts
+            .inconvs
+            .iter()
+            .flat_map(|inconv| {
+                opts.outconvs
+                    .iter()
+                    .filter(move |outconv| {
+                        inconv.radix().is_some()
+                            && outconv.radix().is_some()
+                            && inconv.radix() != outconv.radix()
+                    })
+                    .map(move |outconv| (*inconv, *outconv))
+            })
+            .collect();
+
+        if pairs.is_empty() {
+            None
+        } else {
+            Some(Self {
+                rng: Rng(seed),
+                // Values must stay positive in the intermediate type
+                width: width.min(IntermediateValue::BITS - 1),
+                pairs,
+            })
+        }
+    }
+
+    /// Draw next question
+    pub fn question(&mut self) -> Question {
+        let (from, to) = self.pairs[self.rng.below(self.pairs.len() as u64) as usize];
+        let bits = (self.rng.next() as u128) << 64 | self.rng.next() as u128;
+        let value = (bits >> (128 - self.width)) as IntermediateValue;
+        Question { value, from, to }
+    }
+}
+
+impl Question {
+    /// Value as written in the base to convert from
+    pub fn prompt(&self) -> String {
+        matching_outconv(self.from)
+            .and_then(|outconv| outconv.get_converter(None).convert(self.value).ok())
+            .unwrap_or_else(|| self.value.to_string())
+    }
+
+    /// Expected answer, as written in the base to convert to
+    pub fn answer(&self) -> String {
+        self.to
+            .get_converter(None)
+            .convert(self.value)
+            .unwrap_or_else(|_| self.value.to_string())
+    }
+
+    /// Check an answer by reading it with the input converter of the target base
+    pub fn check(&self, answer: &str) -> Result<bool, InputError> {
+        match matching_inconv(self.to) {
+            Some(inconv) => Ok(inconv.get_converter().convert(answer.trim())? == self.value),
+            None => Ok(false),
+        }
+    }
+}
+
+/// Input converter reading the digits of an output converter
+fn matching_inconv(outconv: OutputConverterType) -> Option<InputConverterType> {
+    InputConverterType::iter()
+        .find(|inconv| outconv.radix().is_some() && inconv.radix() == outconv.radix())
+}
+
+/// Output converter writing the digits of an input converter
+fn matching_outconv(inconv: InputConverterType) -> Option<OutputConverterType> {
+    OutputConverterType::iter()
+        .find(|outconv| inconv.radix().is_some() && outconv.radix() == inconv.radix())
+}
+
+/// Run an interactive quiz session on stdin and stdout
+pub fn run_quiz(settings: &QuizSettings, opts: &Opts) -> Result<(), ConversionError> {
+    let seed = settings.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64)
+    });
+    let mut quiz = Quiz::new(seed, settings.width, opts).ok_or(ConversionError::NoQuizBases)?;
+
+    println!(
+        "{} seed {}, {} questions of {} bits",
+        "quiz:".format_heading(),
+        seed,
+        settings.count,
+        settings.width
+    );
+
+    let mut lines = io::stdin().lock().lines();
+    let mut score = Score {
+        correct: 0,
+        asked: 0,
+        time: Duration::ZERO,
+    };
+    for i in 1..=settings.count {
+        let question = quiz.question();
+        println!(
+            "{} {}",
+            format!("{}/{}", i, settings.count)
+                .as_str()
+                .format_heading(),
+            question
+        );
+        print!("> ");
+        io::stdout().flush().map_err(ConversionError::ReadInput)?;
+
+        // Stop at end of input
+        let start = Instant::now();
+        let Some(line) = lines.next() else {
+            println!();
+            break;
+        };
+        let line = line.map_err(ConversionError::ReadInput)?;
+        let time = start.elapsed();
+
+        score.asked += 1;
+        score.time += time;
+        match question.check(&line) {
+            Ok(true) => {
+                score.correct += 1;
+                println!("{} ({:.1}s)", "correct".format_value(), time.as_secs_f64());
+            }
+            Ok(false) => println!(
+                "{}, the answer is {} ({:.1}s)",
+                "wrong".format_error(),
+                question.answer(),
+                time.as_secs_f64()
+            ),
+            Err(err) => println!(
+                "{}: {}, the answer is {} ({:.1}s)",
+                "invalid".format_error(),
+                err,
+                question.answer(),
+                time.as_secs_f64()
+            ),
+        }
+    }
+
+    println!("{}", score);
+    Ok(())
+}
+
+/// Small, seedable pseudo-random number generator (SplitMix64)
+///
+/// Sequences only depend on the seed, so that sessions can be repeated
+struct Rng(u64);
+
+impl Rng {
+    /// Next pseudo-random number
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Next pseudo-random number below a bound
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::indexset;
+
+    use super::*;
+
+    #[test]
+    fn quiz_question_ok() {
+        let opts = Opts {
+            inconvs: indexset![InputConverterType::DEC, InputConverterType::ASCII],
+            outconvs: indexset![OutputConverterType::DEC, OutputConverterType::HEX],
+            ..Default::default()
+        };
+        let questions = |seed| {
+            let mut quiz = Quiz::new(seed, 8, &opts).unwrap();
+            (0..20).map(|_| quiz.question()).collect::<Vec<_>>()
+        };
+
+        // Same seed, same session
+        assert_eq!(questions(42), questions(42));
+        assert_ne!(questions(42), questions(43));
+
+        for question in questions(7) {
+            assert_eq!(question.from, InputConverterType::DEC);
+            assert_eq!(question.to, OutputConverterType::HEX);
+            assert!((0..256).contains(&question.value));
+        }
+    }
+
+    #[test]
+    fn quiz_new_err() {
+        let opts = Opts {
+            inconvs: indexset![InputConverterType::HEX, InputConverterType::ASCII],
+            outconvs: indexset![OutputConverterType::HEX, OutputConverterType::ASCII],
+            ..Default::default()
+        };
+        assert!(Quiz::new(0, 8, &opts).is_none());
+    }
+
+    #[test]
+    fn question_check_ok() {
+        let question = Question {
+            value: 0x2A,
+            from: InputConverterType::DEC,
+            to: OutputConverterType::HEX,
+        };
+        assert_eq!(question.prompt(), "42");
+        assert_eq!(question.answer(), "2A");
+
+        let tests = [
+            ("2A", Ok(true)),
+            (" 0x2a\n", Ok(true)),
+            ("2B", Ok(false)),
+            ("2G", Err(InputError::InvalidCharacter(1, 'G'))),
+        ];
+        for (answer, exp) in tests {
+            assert_eq!(question.check(answer), exp);
+        }
+    }
+}
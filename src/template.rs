@@ -0,0 +1,234 @@
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, digit1, none_of, one_of},
+    combinator::{all_consuming, map, map_res, opt, value},
+    multi::{many0, many1},
+    sequence::{delimited, pair, preceded, tuple},
+    IResult,
+};
+use serde::{de, Deserialize};
+use std::str::FromStr;
+
+use crate::{
+    convert::{IntermediateValue, OutputConverterType, OutputError},
+    opts::Aliases,
+};
+
+/// User-defined output format
+///
+/// Example: `{hex:08} ({dec}) {bin:>32}`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Template {
+    parts: Vec<TemplatePart>,
+}
+
+/// Piece of a format template
+#[derive(Debug, PartialEq, Clone)]
+enum TemplatePart {
+    /// Text copied as is
+    Literal(String),
+    /// Value produced by an output converter
+    Value(OutputConverterType, FormatSpec),
+}
+
+/// Padding of a value in a format template
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+struct FormatSpec {
+    left_align: bool,
+    zero_pad: bool,
+    width: usize,
+}
+
+impl Template {
+    /// Render template for a given value
+    ///
+    /// Fails if any of the output converters fails, giving the first that does
+    pub fn render(
+        &self,
+        input: IntermediateValue,
+        width: Option<u32>,
+    ) -> Result<String, (OutputConverterType, OutputError)> {
+        let mut res = String::new();
+        for part in &self.parts {
+            match part {
+                TemplatePart::Literal(text) => res += text,
+                TemplatePart::Value(outconv, spec) => {
+                    let value = outconv
+                        .get_converter(width)
+                        .convert(input)
+                        .map_err(|err| (*outconv, err))?;
+                    res += &spec.pad(&value)
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl FormatSpec {
+    /// Pad value to the requested width
+    fn pad(&self, value: &str) -> String {
+        let len = value.chars().count();
+        if len >= self.width {
+            return value.to_string();
+        }
+        let fill = self.width - len;
+
+        if self.zero_pad {
+            // Zeros go after the sign
+            let (sign, digits) = match value.strip_prefix('-') {
+                Some(digits) => ("-", digits),
+                None => ("", value),
+            };
+            format!("{}{}{}", sign, "0".repeat(fill), digits)
+        } else if self.left_align {
+            format!("{}{}", value, " ".repeat(fill))
+        } else {
+            format!("{}{}", " ".repeat(fill), value)
+        }
+    }
+}
+
+impl Template {
+    /// Parse template, looking up converter names in user aliases first
+    pub fn parse_with(s: &str, aliases: &Aliases) -> Result<Self, ()> {
+        let (_, parts) = all_consuming(many0(alt((parse_literal, |input| {
+            parse_placeholder(input, aliases)
+        }))))(s)
+        .map_err(|_| ())?;
+        Ok(Self { parts })
+    }
+}
+
+impl FromStr for Template {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Aliases::default())
+    }
+}
+
+impl<'de> Deserialize<'de> for Template {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| de::Error::custom(format!("Invalid format template: {}", s)))
+    }
+}
+
+/// Parse literal text, with {{ and }} standing for single braces
+fn parse_literal(input: &str) -> IResult<&str, TemplatePart> {
+    map(
+        many1(alt((
+            none_of("{}"),
+            value('{', tag("{{")),
+            value('}', tag("}}")),
+        ))),
+        |chars| TemplatePart::Literal(chars.into_iter().collect()),
+    )(input)
+}
+
+/// Parse placeholder, with a converter name or alias. Ex: {hex:08}, {one_hot}
+fn parse_placeholder<'a>(input: &'a str, aliases: &Aliases) -> IResult<&'a str, TemplatePart> {
+    map(
+        delimited(
+            char('{'),
+            pair(
+                map_res(
+                    take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_'),
+                    |name: &str| aliases.parse::<OutputConverterType>(name),
+                ),
+                opt(preceded(char(':'), parse_format_spec)),
+            ),
+            char('}'),
+        ),
+        |(outconv, spec)| TemplatePart::Value(outconv, spec.unwrap_or_default()),
+    )(input)
+}
+
+/// Parse padding specification. Format: [<|>][0][WIDTH]
+fn parse_format_spec(input: &str) -> IResult<&str, FormatSpec> {
+    map(
+        tuple((
+            opt(one_of("<>")),
+            opt(char('0')),
+            opt(map_res(digit1, usize::from_str)),
+        )),
+        |(align, zero, width)| FormatSpec {
+            left_align: align == Some('<'),
+            zero_pad: zero.is_some(),
+            width: width.unwrap_or(0),
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_render_ok() {
+        let tests = [
+            (
+                "{hex:08} ({dec}) {bin:>12}",
+                0xA5,
+                "000000A5 (165)     10100101",
+            ),
+            ("{DEC:<5}|", 42, "42   |"),
+            ("{dec:05}", -42, "-0042"),
+            ("{{{oct}}}", 8, "{10}"),
+            ("0x{hex:2}", 0x1234, "0x1234"),
+            ("plain text", 1, "plain text"),
+            ("{xs3} {packed_bcd} {unpacked_bcd}", 59, "8C 59 0509"),
+            ("{one_hot} {thermo}", 3, "1000 111"),
+            ("{x}-{b}", 5, "5-101"),
+            (
+                "{bits}",
+                6,
+                "set 2 1; popcount 2; lz 5; tz 1; highest 2; pow2 no; next 8; prev 4; 8 bits",
+            ),
+        ];
+        for (template, input, exp) in tests {
+            let template: Template = template.parse().unwrap();
+            assert_eq!(template.render(input, None).unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn template_render_err() {
+        let template: Template = "{dec} {hex}".parse().unwrap();
+        assert_eq!(
+            template.render(-1, None),
+            Err((OutputConverterType::HEX, OutputError::Negative))
+        );
+    }
+
+    #[test]
+    fn template_parse_aliases() {
+        let aliases = Aliases::from([("bcd8", "packed_bcd")]);
+        let template = Template::parse_with("{bcd8}", &aliases).unwrap();
+        assert_eq!(template.render(42, None).unwrap(), "42");
+        "{bcd8}".parse::<Template>().unwrap_err();
+    }
+
+    #[test]
+    fn template_parse_err() {
+        let tests = [
+            "{hx}",
+            "{hex",
+            "hex}",
+            "{hex:08x}",
+            "{}",
+            "{hex:-4}",
+            "{hex-2}",
+            "{xs4}",
+        ];
+        for input in tests {
+            input.parse::<Template>().unwrap_err();
+        }
+    }
+}
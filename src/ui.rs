@@ -1,15 +1,37 @@
-use colored::{ColoredString, Colorize};
+mod theme;
+
+use colored::ColoredString;
 use core::fmt;
-use std::{env, fmt::Display};
+use indexmap::IndexSet;
+use serde::{de, Deserialize};
+use std::{
+    env,
+    fmt::Display,
+    io::{self, IsTerminal},
+    iter,
+    str::FromStr,
+    sync::Mutex,
+};
+use terminal_size::{terminal_size, Width};
+use theme::theme;
 
 use crate::{
+    constants::{
+        DEFAULT_TERMINAL_WIDTH, DIAGRAM_ROW_BITS, ENUM_CONVERTER, FLAGS_CONVERTER, GRID_COLUMN_GAP,
+        QUIZ_COMMAND, REGISTER_CONVERTER,
+    },
     convert::{
-        ConversionError, ConversionOutput, ConversionResult, InputConverterType,
-        OutputConverterType,
+        AlreadyInUse, BitDiagram, BitOperation, ConversionError, ConversionOutput,
+        ConversionResult, ConversionTable, InputConverterType, InputError, OutputConverterType,
+        OutputError, Rejections, SvdError,
     },
+    explain::Explanation,
     opts::OptsBuildError,
+    quiz::{Question, Score},
 };
 
+pub use theme::{set_theme, Theme};
+
 /// Custom colors used in printing
 pub trait ColorPalette {
     /// Format text as a heading
@@ -24,31 +46,116 @@ pub trait ColorPalette {
     fn format_value(&self) -> ColoredString;
     /// Format text as a error
     fn format_error(&self) -> ColoredString;
+    /// Format text as a secondary note
+    fn format_note(&self) -> ColoredString;
 }
 
-impl<'a> ColorPalette for &'a str {
+impl ColorPalette for &str {
     fn format_heading(&self) -> ColoredString {
-        self.bold().green()
+        theme().heading.apply(self)
     }
 
     fn format_heading_nobold(&self) -> ColoredString {
-        self.green()
+        theme().heading.nobold().apply(self)
     }
 
     fn format_subheading(&self) -> ColoredString {
-        self.normal()
+        theme().subheading.apply(self)
     }
 
     fn format_binary(&self) -> ColoredString {
-        self.bold().bright_white()
+        theme().binary.apply(self)
     }
 
     fn format_value(&self) -> ColoredString {
-        self.blue().bold()
+        theme().value.apply(self)
     }
 
     fn format_error(&self) -> ColoredString {
-        self.red().bold()
+        theme().error.apply(self)
+    }
+
+    fn format_note(&self) -> ColoredString {
+        theme().note.apply(self)
+    }
+}
+
+/// When to colour output
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ColorMode {
+    /// Colour when stdout is a terminal, following NO_COLOR and CLICOLOR_FORCE
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ColorMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| de::Error::custom(format!("Invalid color mode: {}", s)))
+    }
+}
+
+/// Colour mode last selected, kept to decide again for stderr
+static COLOR_MODE: Mutex<ColorMode> = Mutex::new(ColorMode::Auto);
+
+/// Enable or disable colours in everything printed to stdout from now on
+pub fn set_color_mode(mode: ColorMode) {
+    *COLOR_MODE.lock().unwrap() = mode;
+    colored::control::set_override(colorize(mode, io::stdout().is_terminal()));
+}
+
+/// Enable or disable colours in everything printed from now on, as decided for stderr
+///
+/// Used before printing help, version and errors
+pub fn use_stderr_colors() {
+    let mode = *COLOR_MODE.lock().unwrap();
+    colored::control::set_override(colorize(mode, io::stderr().is_terminal()));
+}
+
+/// Decide whether to colour output printed to a stream, given whether it is a TTY
+fn colorize(mode: ColorMode, tty: bool) -> bool {
+    match mode {
+        ColorMode::Always => true,
+        ColorMode::Never => false,
+        ColorMode::Auto => colorize_from_env(
+            env::var("NO_COLOR").ok(),
+            env::var("CLICOLOR_FORCE").ok(),
+            tty,
+        ),
+    }
+}
+
+/// Decide whether to colour output from the environment
+///
+/// CLICOLOR_FORCE takes priority over NO_COLOR, which takes priority over TTY detection.
+/// NO_COLOR counts as set when not empty, CLICOLOR_FORCE when neither empty nor "0".
+fn colorize_from_env(no_color: Option<String>, clicolor_force: Option<String>, tty: bool) -> bool {
+    let no_color = no_color.is_some_and(|val| !val.is_empty());
+    let clicolor_force = clicolor_force.is_some_and(|val| !val.is_empty() && val != "0");
+    if clicolor_force {
+        true
+    } else if no_color {
+        false
+    } else {
+        tty
     }
 }
 
@@ -57,7 +164,7 @@ pub fn usage() -> String {
     let bin = env::args().next().unwrap();
     let mut res = String::new();
     res += &format!(
-        "{} {} [-h] [<input converter>] <value> [<output converters>]",
+        "{} {} [<options>] [--] [<input converter>] <value> [<output converters>]",
         "Usage:".format_heading(),
         bin
     );
@@ -86,20 +193,106 @@ pub fn help() -> String {
     );
     res += &format!("\n{}\n", usage());
     res += &format!("\n{}\n", "Options:".format_heading());
-    res += &format!("  -h: display this message\n");
+    res += "  -h, --help: display this message\n";
+    res += "  -v, --version: display version\n";
+    res += "  -a, --all: also show unlikely interpretations of the value\n";
+    res += "  -i, --from <converter>: input converter to use\n";
+    res += "  -o, --to <converters>: comma separated output converters to use\n";
+    res += "  --csv: output a comma separated table\n";
+    res += "  --tsv: output a tab separated table\n";
+    res += "  -r, --raw: output only the value of the single result\n";
+    res += "  --layout <list|grid>: arrangement of the results\n";
+    res += "  --format <template>: output format, ex: \"{hex:08} ({dec})\"\n";
+    res += "  --color <auto|always|never>: when to use colors\n";
+    res += "  --explain: show how each result is derived, step by step\n";
+    res += "  --diagram: draw the bits of the value in boxes\n";
+    res += "  --width <bits>: width of the values, for bit level converters and diagrams\n";
+    res += "  --svd <file>: read register definitions from a CMSIS-SVD file\n";
+    res += "  --: stop parsing options, ex: to convert -42\n";
+    res += &format!("\n{}\n", "Operations, applied in order:".format_heading());
+    res += "  --rotl <n>, --rotr <n>: rotate left or right by n bits within the width\n";
+    res += "  --reverse: reverse the order of the bits within the width\n";
+    res += "  --bswap: reverse the order of the bytes within the width\n";
+    res += "  --sext <bits>: sign-extend from the given width, ex: --sext 8 turns 0x80 into -128\n";
+    res += "  --zext <bits>: zero-extend from the given width, ex: --zext 8 turns -128 into 0x80\n";
+    res += "  --trunc <bits>: keep the lowest bits only\n";
+    res += &format!("\n{}\n", "Values:".format_heading());
+    res += "  -: read values from stdin, one per line\n";
+    res += "  =<value>: always read as a value, ex: =dec for hexadecimal 0xDEC\n";
+    res += "  <value>[high:low]: extract a bit range, ex: 0xDEADBEEF[15:8]\n";
+    res += "  <value>[high:low]=<bits>: replace a bit range, ex: 0xDEADBEEF[15:8]=0x42\n";
+    res += &format!("\n{}\n", "Quiz:".format_heading());
+    res += &format!(
+        "  {} {} [<options>]: practice base conversions\n",
+        bin, QUIZ_COMMAND
+    );
+    res += "  -i, --from <converter>, -o, --to <converters>: bases of the questions\n";
+    res += "  --width <bits>: width of the values, 8 by default\n";
+    res += "  --count <number>: number of questions, 10 by default\n";
+    res += "  --seed <number>: repeat a session with the same questions\n";
     res += &format!("\n{}\n", "Input converters:".format_heading());
-    res += &format!("  DEC: decimal\n");
-    res += &format!("  BIN: binary\n");
-    res += &format!("  HEX: hexadecimal\n");
-    res += &format!("  OCT: octal\n");
-    res += &format!("  ASCII: ascii character\n");
+    res += "  DEC (d, decimal): decimal\n";
+    res += "  BIN (b, binary): binary\n";
+    res += "  GRAY: binary reflected Gray code\n";
+    res += "  BCD (packed_bcd): packed BCD, one decimal digit per nibble, in hexadecimal\n";
+    res += "  UBCD (unpacked_bcd): unpacked BCD, one decimal digit per byte, in hexadecimal\n";
+    res += "  XS3 (excess3): excess-3, each decimal digit plus 3 per nibble, in hexadecimal\n";
+    res += "  ONEHOT (one_hot): one-hot code, only the bit of the value set\n";
+    res +=
+        "  THERMO (thermometer, unary): thermometer code, as many lowest bits set as the value\n";
+    res += "  HEX (x, h, hexadecimal): hexadecimal\n";
+    res += "  OCT (o, octal): octal\n";
+    res += "  ASCII (char): ascii character\n";
+    res += "  CODE: name of an errno, signal, exit status, HTTP status or Windows code\n";
+    res += "  SYMBOL (sym, const): name of a constant or enumeration value of the config file\n";
+    res += "  MASK: bit range whose bits are set, ex: 15:8 for 0xFF00\n";
+    res += &format!(
+        "  {} <register>: assignments to the fields of a register, ex: EN=1,MODE=7E1\n",
+        REGISTER_CONVERTER.to_ascii_uppercase()
+    );
+    res += &format!(
+        "  {} <set>: names of flags joined by |, ex: O_CREAT|O_RDWR\n",
+        FLAGS_CONVERTER.to_ascii_uppercase()
+    );
     res += &format!("\n{}\n", "Output converters:".format_heading());
-    res += &format!("  DEC: decimal\n");
-    res += &format!("  BIN: binary\n");
-    res += &format!("  HEX: hexadecimal\n");
-    res += &format!("  OCT: octal\n");
-    res += &format!("  ASCII: ascii character\n");
+    res += "  DEC (d, decimal): decimal\n";
+    res += "  BIN (b, binary): binary\n";
+    res += "  GRAY: binary reflected Gray code\n";
+    res += "  BCD (packed_bcd): packed BCD, one decimal digit per nibble, in hexadecimal\n";
+    res += "  UBCD (unpacked_bcd): unpacked BCD, one decimal digit per byte, in hexadecimal\n";
+    res += "  XS3 (excess3): excess-3, each decimal digit plus 3 per nibble, in hexadecimal\n";
+    res += "  ONEHOT (one_hot): one-hot code, only the bit of the value set\n";
+    res +=
+        "  THERMO (thermometer, unary): thermometer code, as many lowest bits set as the value\n";
+    res += "  HEX (x, h, hexadecimal): hexadecimal\n";
+    res += "  OCT (o, octal): octal\n";
+    res += "  ASCII (char): ascii character\n";
+    res += "  BITS (breakdown): set bits, popcount, leading and trailing zeros\n";
+    res += "  ERRNO: Linux errno name and message, also negated as returned by syscalls\n";
+    res += "  SIGNAL: Linux signal name and description\n";
+    res += "  EXIT: meaning of a process exit status, ex: SIGSEGV (128+11)\n";
+    res += "  HTTP: HTTP status reason phrase and class\n";
+    res += "  HRESULT: Windows HRESULT severity, facility and code\n";
+    res += "  NTSTATUS: Windows NTSTATUS severity, facility and code\n";
+    res += "  CAST (as): value after a cast to each of u8 to i128, and whether it is lossy\n";
+    res += &format!(
+        "  {} <register>: fields of a register declared in the config file or an SVD file\n",
+        REGISTER_CONVERTER.to_ascii_uppercase()
+    );
+    res += &format!(
+        "  {} <set>: named flags of a value, built-in sets: open, prot, mode\n",
+        FLAGS_CONVERTER.to_ascii_uppercase()
+    );
+    res += &format!(
+        "  {} <name>: name of a value in an enumeration declared in the config file\n",
+        ENUM_CONVERTER.to_ascii_uppercase()
+    );
     res += &format!("\n{} {} dec 1234 bin hex", "Example:".format_heading(), bin);
+    res += &format!(
+        "\n{} seq 0 15 | {} --csv - hex bin",
+        "Batch:".format_heading(),
+        bin
+    );
     res
 }
 
@@ -108,14 +301,12 @@ impl<'a> Display for OptsBuildError<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Args(err) => {
-                write!(f, "{}\n", err)?;
+                writeln!(f, "{}", err)?;
                 write!(f, "{}", usage())
             }
-            Self::Config(err) => write!(
-                f,
-                "invalid configuration in config file:\n{}",
-                err.to_string()
-            ),
+            Self::Config(err) => write!(f, "invalid configuration in config file:\n{}", err),
+            Self::Svd(path, err) => write!(f, "cannot load SVD file {}: {}", path.display(), err),
+            Self::Definitions(err) => write!(f, "{}", err),
         }
     }
 }
@@ -124,21 +315,165 @@ impl<'a> Display for OptsBuildError<'a> {
 impl Display for ConversionError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self {
-            ConversionError::NoResults => {
-                write!(f, "no results")
+            ConversionError::NoResults(rejections) => write!(f, "{}", rejections),
+            ConversionError::ReadInput(err) => {
+                write!(f, "unable to read input values: {}", err)
+            }
+            ConversionError::AmbiguousRawOutput(count) => {
+                write!(
+                    f,
+                    "raw output needs exactly one result, but {} were produced: \
+                     select a single input and output converter",
+                    count
+                )
+            }
+            ConversionError::SkippedRawOutput(inconv, outconv, err) => {
+                write!(f, "cannot convert from {} to {}: {}", inconv, outconv, err)
+            }
+            ConversionError::NoQuizBases => {
+                write!(
+                    f,
+                    "quiz needs two different numeric bases, ex: --from dec --to hex"
+                )
             }
         }
     }
 }
 
+// Reasons for rejecting an input value, with a caret under the offending character
+impl Display for Rejections {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Accepted, but every output was skipped or left out
+        if !self.skipped.is_empty() || !self.omitted.is_empty() {
+            write!(f, "no results for \"{}\"", self.input)?;
+            for (inconv, outconv, err) in &self.skipped {
+                write!(f, "\n  {} to {}: skipped, {}", inconv, outconv, err)?;
+            }
+            for (inconv, outconv) in &self.omitted {
+                write!(
+                    f,
+                    "\n  {} to {}: omitted, same as the input",
+                    inconv, outconv
+                )?;
+            }
+            return Ok(());
+        }
+        if self.inner.is_empty() {
+            return write!(f, "no results");
+        }
+
+        write!(f, "no valid interpretation of \"{}\"", self.input)?;
+        for (inconv, err) in &self.inner {
+            write!(f, "\n  {}: {}", inconv, err)?;
+            if let Some(column) = err.column() {
+                write!(
+                    f,
+                    "\n    {}\n    {}{}",
+                    self.input,
+                    " ".repeat(column),
+                    "^".format_error()
+                )?;
+            }
+        }
+        if let Some(inconv) = &self.hint {
+            write!(
+                f,
+                "\nhint: \"{}\" is a {}, read it with: baseic {} {}",
+                self.input,
+                inconv,
+                inconv.names()[0].to_lowercase(),
+                self.input
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Definitions set too late
+impl Display for AlreadyInUse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} already in use, cannot be set again", self.0)
+    }
+}
+
+// Input converter error
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty value"),
+            Self::EmptyAfterPrefix(prefix) => write!(f, "no digits after \"{}\"", prefix),
+            Self::InvalidCharacter(column, c) => {
+                write!(f, "invalid character {:?} at column {}", c, column + 1)
+            }
+            Self::Overflow => write!(f, "value out of the representable range"),
+            Self::MissingFieldValue(assignment) => {
+                write!(
+                    f,
+                    "missing value in \"{}\", expected FIELD=value",
+                    assignment
+                )
+            }
+            Self::UnknownField(name) => write!(f, "unknown field \"{}\"", name),
+            Self::InvalidFieldValue(name, value) => {
+                write!(f, "invalid value \"{}\" for field {}", value, name)
+            }
+            Self::UnknownFlag(name) => write!(f, "unknown flag \"{}\"", name),
+            Self::UnknownName(name) => write!(f, "unknown code name \"{}\"", name),
+            Self::UnknownSymbol(name) => write!(f, "unknown symbol \"{}\"", name),
+            Self::DoesNotFit(width) => write!(f, "value does not fit in {} bits", width),
+            Self::NotWholeBytes(width) => write!(f, "cannot swap the bytes of {} bits", width),
+            Self::NoBitSet => write!(f, "no bit set, expected exactly one"),
+            Self::InvalidBitRange(range) => {
+                write!(
+                    f,
+                    "invalid bit range \"{}\", expected high:low or bit",
+                    range
+                )
+            }
+            Self::InvalidRangeValue(value, width) => {
+                write!(
+                    f,
+                    "invalid value \"{}\" for a range of {} bits",
+                    value, width
+                )
+            }
+        }
+    }
+}
+
+// SVD file error
+impl Display for SvdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(err) => write!(f, "{}", err),
+            Self::Xml(err) => write!(f, "{}", err),
+            Self::Invalid(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+// Output converter error
+impl Display for OutputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Negative => write!(f, "negative value has no unsigned representation"),
+            Self::NotPrintable => write!(f, "not a printable ASCII character"),
+            Self::DoesNotFit(width) => write!(f, "value does not fit in {} bits", width),
+            Self::UnknownCode => write!(f, "not a known code"),
+            Self::NotInEnum => write!(f, "not a value of the enumeration"),
+            Self::NoFields => write!(f, "register has no fields"),
+        }
+    }
+}
+
 // Full conversion output
 impl Display for ConversionOutput {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for (inconv, conv_res) in &self.inner {
             let inconv_str: &str = &inconv.to_string();
-            write!(
+            writeln!(
                 f,
-                "{} {}{}\n",
+                "{} {}{}",
                 "from".format_heading_nobold(),
                 inconv_str.format_heading(),
                 ":".format_heading()
@@ -154,64 +489,486 @@ impl Display for ConversionOutput {
 // Conversion result from a single input converter
 impl Display for ConversionResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let max_outconv_len = OutputConverterType::max_str_len();
+        // Register names may be longer than the other output converter names
+        let max_outconv_len = self
+            .inner
+            .keys()
+            .chain(self.skipped.keys())
+            .map(|outconv| outconv.to_string().len())
+            .fold(OutputConverterType::max_str_len(), usize::max);
         for (outconv, val) in &self.inner {
             let outconv: &str = &right_align(&outconv.to_string(), max_outconv_len);
-            let val: &str = &val;
-            write!(
+            let val: &str = val;
+            writeln!(
                 f,
-                "  {}{} {}\n",
+                "  {}{} {}",
                 outconv.format_subheading(),
                 ":".format_subheading(),
                 val.format_value()
             )?;
         }
+        for (outconv, err) in &self.skipped {
+            let outconv = right_align(&outconv.to_string(), max_outconv_len);
+            let line = format!("  {}: skipped, {}", outconv, err);
+            writeln!(f, "{}", line.as_str().format_note())?;
+        }
         Ok(())
     }
 }
 
-// Used for printing
-impl ToString for InputConverterType {
-    fn to_string(&self) -> String {
-        match self {
-            InputConverterType::DEC => {
-                format!("decimal")
-            }
-            InputConverterType::BIN => {
-                format!("binary")
-            }
-            InputConverterType::HEX => {
-                format!("hexadecimal")
-            }
-            InputConverterType::OCT => {
-                format!("octal")
+// Step-by-step derivation of the results from one input converter
+impl Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inconv_str: &str = &self.inconv.to_string();
+        writeln!(
+            f,
+            "{} {}{}",
+            "from".format_heading_nobold(),
+            inconv_str.format_heading(),
+            ":".format_heading()
+        )?;
+        for step in &self.input_steps {
+            writeln!(f, "  {}", step)?;
+        }
+
+        for (outconv, steps) in &self.outputs {
+            let outconv_str = format!("to {}:", outconv);
+            writeln!(f, "  {}", outconv_str.as_str().format_subheading())?;
+            for step in steps {
+                writeln!(f, "    {}", step)?;
             }
-            InputConverterType::ASCII => {
-                format!("ascii")
+        }
+        Ok(())
+    }
+}
+
+// Bits of a value in boxes, with bit numbers above and hex digits below
+impl Display for BitDiagram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inconv_str: &str = &self.inconv.to_string();
+        writeln!(
+            f,
+            "{} {}{}",
+            "from".format_heading_nobold(),
+            inconv_str.format_heading(),
+            ":".format_heading()
+        )?;
+
+        let bits = match self.bits {
+            Ok(bits) => bits,
+            Err(err) => {
+                let line = format!("  skipped, {}", err);
+                return writeln!(f, "{}", line.as_str().format_note());
             }
+        };
+
+        // One box per row of bits, starting from the highest
+        let mut high = self.width;
+        while high > 0 {
+            let low = (high - 1) / DIAGRAM_ROW_BITS * DIAGRAM_ROW_BITS;
+            write_diagram_row(f, bits, high, low)?;
+            high = low;
         }
+        Ok(())
     }
 }
 
-// Used for printing
-impl ToString for OutputConverterType {
-    fn to_string(&self) -> String {
-        match self {
-            OutputConverterType::DEC => {
-                format!("decimal")
+/// Draw the bits of a value from high (excluded) to low in a box
+///
+/// Nibbles are separated by |, bytes by ||
+fn write_diagram_row(f: &mut fmt::Formatter<'_>, bits: u128, high: u32, low: u32) -> fmt::Result {
+    let (mut numbers, mut border, mut cells, mut digits) =
+        (String::new(), String::new(), String::new(), String::new());
+
+    let mut nibble_high = high;
+    while nibble_high > low {
+        let nibble_low = ((nibble_high - 1) / 4 * 4).max(low);
+        let sep = if nibble_high != high && nibble_high.is_multiple_of(8) {
+            "||"
+        } else {
+            "|"
+        };
+        let indexes: Vec<u32> = (nibble_low..nibble_high).rev().collect();
+        let body_len = indexes.len() * 4 - 1;
+
+        // Bit numbers, centered above their cell
+        numbers += &" ".repeat(sep.len());
+        let nibble_numbers: Vec<String> = indexes
+            .iter()
+            .map(|index| format!("{:^3}", index))
+            .collect();
+        numbers += &nibble_numbers.join(" ").as_str().format_note().to_string();
+
+        border += &"+".repeat(sep.len());
+        border += &"-".repeat(body_len);
+
+        // Set bits stand out from the others
+        cells += sep;
+        let nibble_cells: Vec<String> = indexes
+            .iter()
+            .map(|index| match bits >> index & 1 {
+                1 => format!(" {} ", "1".format_value()),
+                _ => format!(" {} ", "0".format_note()),
+            })
+            .collect();
+        cells += &nibble_cells.join(" ");
+
+        // Hex digit, centered below its nibble
+        let digit = format!("{:X}", bits >> nibble_low & 0xF);
+        digits += &" ".repeat(sep.len());
+        digits += &format!("{:^1$}", digit, body_len)
+            .as_str()
+            .format_heading()
+            .to_string();
+
+        nibble_high = nibble_low;
+    }
+
+    writeln!(f, "  {}", numbers.trim_end())?;
+    writeln!(f, "  {}+", border)?;
+    writeln!(f, "  {}|", cells)?;
+    writeln!(f, "  {}+", border)?;
+    writeln!(f, "  {}", digits.trim_end())
+}
+
+// Quiz question
+impl Display for Question {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prompt = self.prompt();
+        write!(
+            f,
+            "convert {} {} to {}",
+            self.from,
+            prompt.as_str().format_value(),
+            self.to
+        )
+    }
+}
+
+// Quiz session result
+impl Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let score = format!("{}/{}", self.correct, self.asked);
+        write!(
+            f,
+            "{} {} in {:.1}s",
+            "score:".format_heading(),
+            score.as_str().format_value(),
+            self.time.as_secs_f64()
+        )?;
+        if self.asked > 0 {
+            write!(
+                f,
+                ", {:.1}s per answer",
+                self.time.as_secs_f64() / self.asked as f64
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Get width of the terminal attached to stdout
+pub fn terminal_width() -> usize {
+    terminal_size().map_or(DEFAULT_TERMINAL_WIDTH, |(Width(width), _)| width as usize)
+}
+
+/// Conversion output rendered as a grid, input converters by output converters
+pub struct Grid<'a> {
+    output: &'a ConversionOutput,
+    outconvs: &'a IndexSet<OutputConverterType>,
+    width: usize,
+}
+
+impl ConversionOutput {
+    /// Render output as a grid with columns in the given order, fitting in the given width
+    pub fn grid<'a>(
+        &'a self,
+        outconvs: &'a IndexSet<OutputConverterType>,
+        width: usize,
+    ) -> Grid<'a> {
+        Grid {
+            output: self,
+            outconvs,
+            width,
+        }
+    }
+}
+
+// Conversion output as a grid
+impl Display for Grid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = &self.output.inner;
+        let gap = " ".repeat(GRID_COLUMN_GAP);
+
+        // Compute column widths, skipping output converters without any result
+        let header_width = rows
+            .keys()
+            .map(|inconv| inconv.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let columns: Vec<(OutputConverterType, usize)> = self
+            .outconvs
+            .iter()
+            .filter_map(|outconv| {
+                rows.values()
+                    .filter_map(|res| res.inner.get(outconv))
+                    .map(String::len)
+                    .max()
+                    .map(|len| (*outconv, len.max(outconv.to_string().len())))
+            })
+            .collect();
+
+        // Split columns in chunks that fit the width, each with at least one column
+        let mut chunks: Vec<&[(OutputConverterType, usize)]> = vec![];
+        let (mut start, mut used) = (0, header_width);
+        for (i, (_, width)) in columns.iter().enumerate() {
+            if i > start && used + GRID_COLUMN_GAP + width > self.width {
+                chunks.push(&columns[start..i]);
+                (start, used) = (i, header_width);
             }
-            OutputConverterType::BIN => {
-                format!("binary")
+            used += GRID_COLUMN_GAP + width;
+        }
+        if !columns.is_empty() {
+            chunks.push(&columns[start..]);
+        }
+
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
             }
-            OutputConverterType::HEX => {
-                format!("hexadecimal")
+
+            // Column headers
+            write!(f, "{}", " ".repeat(header_width))?;
+            for (j, (outconv, width)) in chunk.iter().enumerate() {
+                let cell = grid_cell(&outconv.to_string(), *width, j + 1 == chunk.len());
+                write!(f, "{}{}", gap, cell.as_str().format_subheading())?;
+            }
+            writeln!(f)?;
+
+            // One row per input converter
+            for (inconv, res) in rows {
+                let inconv: &str = &right_align(&inconv.to_string(), header_width);
+                write!(f, "{}", inconv.format_heading())?;
+
+                // Stop at the last value, so that rows don't end in padding
+                let values: Vec<&str> = chunk
+                    .iter()
+                    .map(|(outconv, _)| res.inner.get(outconv).map_or("", String::as_str))
+                    .collect();
+                let len = values
+                    .iter()
+                    .rposition(|val| !val.is_empty())
+                    .map_or(0, |j| j + 1);
+                for (j, (val, (_, width))) in values.iter().zip(chunk).take(len).enumerate() {
+                    let cell = grid_cell(val, *width, j + 1 == len);
+                    write!(f, "{}{}", gap, cell.as_str().format_value())?;
+                }
+                writeln!(f)?;
             }
-            OutputConverterType::OCT => {
-                format!("octal")
+        }
+
+        // Output converters without a value have no cell to explain themselves
+        for (inconv, res) in rows {
+            for (outconv, err) in &res.skipped {
+                let line = format!("{} to {}: skipped, {}", inconv, outconv, err);
+                writeln!(f, "{}", line.as_str().format_note())?;
             }
-            OutputConverterType::ASCII => {
-                format!("ascii")
+        }
+        Ok(())
+    }
+}
+
+/// Left align a grid cell, without trailing spaces in the last column
+fn grid_cell(string: &str, size: usize, last: bool) -> String {
+    if last {
+        string.to_string()
+    } else {
+        format!("{:<1$}", string, size)
+    }
+}
+
+/// Delimited text format used to render conversion tables
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TableFormat {
+    CSV,
+    TSV,
+}
+
+impl TableFormat {
+    /// Escape a single field so that it can be placed in a table row
+    fn escape(&self, field: &str) -> String {
+        match self {
+            Self::CSV => {
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.to_string()
+                }
             }
+            Self::TSV => field
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r"),
+        }
+    }
+
+    /// Get field separator
+    fn delimiter(&self) -> char {
+        match self {
+            Self::CSV => ',',
+            Self::TSV => '\t',
+        }
+    }
+
+    /// Join fields into a single table row
+    fn row<'a>(&self, fields: impl IntoIterator<Item = &'a str>) -> String {
+        fields
+            .into_iter()
+            .map(|field| self.escape(field))
+            .collect::<Vec<String>>()
+            .join(&self.delimiter().to_string())
+    }
+}
+
+/// Conversion table rendered in a delimited text format
+pub struct DelimitedTable<'a> {
+    table: &'a ConversionTable,
+    format: TableFormat,
+}
+
+impl ConversionTable {
+    /// Render table in the given delimited text format
+    pub fn delimited(&self, format: TableFormat) -> DelimitedTable<'_> {
+        DelimitedTable {
+            table: self,
+            format,
+        }
+    }
+}
+
+// Conversion table as CSV or TSV
+impl Display for DelimitedTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Header row
+        let headers: Vec<String> = self
+            .table
+            .columns
+            .iter()
+            .map(|(inconv, outconv)| format!("{} to {}", inconv, outconv))
+            .collect();
+        writeln!(
+            f,
+            "{}",
+            self.format.row(
+                iter::once("input")
+                    .chain(headers.iter().map(String::as_str))
+                    .chain(iter::once("skipped"))
+            )
+        )?;
+
+        // One row per input value, empty fields where conversion was not possible
+        for (input, output) in &self.table.rows {
+            let values = self.table.columns.iter().map(|(inconv, outconv)| {
+                output
+                    .inner
+                    .get(inconv)
+                    .and_then(|res| res.inner.get(outconv))
+                    .map_or("", String::as_str)
+            });
+
+            // Last field lists the skipped output converters with their reason
+            let skipped = output
+                .inner
+                .iter()
+                .flat_map(|(inconv, res)| {
+                    res.skipped
+                        .iter()
+                        .map(move |(outconv, err)| format!("{} to {}: {}", inconv, outconv, err))
+                })
+                .collect::<Vec<String>>()
+                .join("; ");
+
+            writeln!(
+                f,
+                "{}",
+                self.format.row(
+                    iter::once(input.as_str())
+                        .chain(values)
+                        .chain(iter::once(skipped.as_str()))
+                )
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Used for printing
+impl Display for InputConverterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputConverterType::DEC => write!(f, "decimal"),
+            InputConverterType::BIN => write!(f, "binary"),
+            InputConverterType::GRAY => write!(f, "Gray code"),
+            InputConverterType::BCD => write!(f, "packed BCD"),
+            InputConverterType::UBCD => write!(f, "unpacked BCD"),
+            InputConverterType::XS3 => write!(f, "excess-3"),
+            InputConverterType::ONEHOT => write!(f, "one-hot"),
+            InputConverterType::THERMO => write!(f, "thermometer"),
+            InputConverterType::HEX => write!(f, "hexadecimal"),
+            InputConverterType::OCT => write!(f, "octal"),
+            InputConverterType::ASCII => write!(f, "ascii"),
+            InputConverterType::CODE => write!(f, "code name"),
+            InputConverterType::SYMBOL => write!(f, "symbol"),
+            InputConverterType::MASK => write!(f, "mask"),
+            InputConverterType::REG(name) => write!(f, "{}", name),
+            InputConverterType::FLAGS(name) => write!(f, "{} flags", name),
+        }
+    }
+}
+
+// Used for printing
+impl Display for OutputConverterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputConverterType::DEC => write!(f, "decimal"),
+            OutputConverterType::BIN => write!(f, "binary"),
+            OutputConverterType::GRAY => write!(f, "Gray code"),
+            OutputConverterType::BCD => write!(f, "packed BCD"),
+            OutputConverterType::UBCD => write!(f, "unpacked BCD"),
+            OutputConverterType::XS3 => write!(f, "excess-3"),
+            OutputConverterType::ONEHOT => write!(f, "one-hot"),
+            OutputConverterType::THERMO => write!(f, "thermometer"),
+            OutputConverterType::HEX => write!(f, "hexadecimal"),
+            OutputConverterType::OCT => write!(f, "octal"),
+            OutputConverterType::ASCII => write!(f, "ascii"),
+            OutputConverterType::BITS => write!(f, "bits"),
+            OutputConverterType::ERRNO => write!(f, "errno"),
+            OutputConverterType::SIGNAL => write!(f, "signal"),
+            OutputConverterType::EXIT => write!(f, "exit status"),
+            OutputConverterType::HTTP => write!(f, "HTTP status"),
+            OutputConverterType::HRESULT => write!(f, "HRESULT"),
+            OutputConverterType::NTSTATUS => write!(f, "NTSTATUS"),
+            OutputConverterType::CAST => write!(f, "casts"),
+            OutputConverterType::REG(name) => write!(f, "{}", name),
+            OutputConverterType::FLAGS(name) => write!(f, "{} flags", name),
+            OutputConverterType::ENUM(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+// Used for printing explanations
+impl Display for BitOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RotateLeft(n) => write!(f, "rotate left by {}", n),
+            Self::RotateRight(n) => write!(f, "rotate right by {}", n),
+            Self::Reverse => write!(f, "reverse bits"),
+            Self::ByteSwap => write!(f, "swap bytes"),
+            Self::SignExtend(width) => write!(f, "sign-extend from {} bits", width),
+            Self::ZeroExtend(width) => write!(f, "zero-extend from {} bits", width),
+            Self::Truncate(width) => write!(f, "truncate to {} bits", width),
         }
     }
 }
@@ -224,3 +981,166 @@ fn right_align(string: &str, size: usize) -> String {
     // Add string
     outstr + string
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::{indexmap, indexset};
+
+    /// Remove the colour escape sequences of rendered text, whatever the colour state
+    fn strip_colors(text: &str) -> String {
+        let mut res = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                res.push(c);
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn table_format_escape_ok() {
+        let tests = [
+            (TableFormat::CSV, "1010", "1010"),
+            (TableFormat::CSV, "','", "\"','\""),
+            (TableFormat::CSV, "'\"'", "\"'\"\"'\""),
+            (TableFormat::TSV, "','", "','"),
+            (TableFormat::TSV, "a\tb", "a\\tb"),
+        ];
+        for (format, input, exp) in tests {
+            assert_eq!(format.escape(input), exp);
+        }
+    }
+
+    #[test]
+    fn delimited_table_ok() {
+        let table = ConversionTable {
+            columns: vec![
+                (InputConverterType::DEC, OutputConverterType::HEX),
+                (InputConverterType::HEX, OutputConverterType::DEC),
+            ],
+            rows: vec![
+                (
+                    "10".to_string(),
+                    ConversionOutput::from(indexmap! {
+                        InputConverterType::DEC => ConversionResult::from(indexmap! {
+                            OutputConverterType::HEX => "A".to_string(),
+                        }),
+                        InputConverterType::HEX => ConversionResult::from(indexmap! {
+                            OutputConverterType::DEC => "16".to_string(),
+                        }),
+                    }),
+                ),
+                (
+                    "A".to_string(),
+                    ConversionOutput::from(indexmap! {
+                        InputConverterType::HEX => ConversionResult::from(indexmap! {
+                            OutputConverterType::DEC => "10".to_string(),
+                        }),
+                    }),
+                ),
+            ],
+        };
+
+        assert_eq!(
+            table.delimited(TableFormat::CSV).to_string(),
+            "input,decimal to hexadecimal,hexadecimal to decimal,skipped\n10,A,16,\nA,,10,\n"
+        );
+        assert_eq!(
+            table.delimited(TableFormat::TSV).to_string(),
+            "input\tdecimal to hexadecimal\thexadecimal to decimal\tskipped\n10\tA\t16\t\nA\t\t10\t\n"
+        );
+    }
+
+    #[test]
+    fn colorize_from_env_ok() {
+        let set = |val: &str| Some(val.to_string());
+        let tests = [
+            (None, None, true, true),
+            (None, None, false, false),
+            (set("1"), None, true, false),
+            (set(""), None, true, true),
+            (set("0"), None, true, false),
+            (None, set("1"), false, true),
+            (set("1"), set("1"), false, true),
+            (None, set("0"), false, false),
+        ];
+        for (no_color, clicolor_force, tty, exp) in tests {
+            assert_eq!(colorize_from_env(no_color, clicolor_force, tty), exp);
+        }
+    }
+
+    #[test]
+    fn grid_ok() {
+        let output = ConversionOutput::from(indexmap! {
+            InputConverterType::DEC => ConversionResult::from(indexmap! {
+                OutputConverterType::HEX => "2A".to_string(),
+                OutputConverterType::BIN => "101010".to_string(),
+            }),
+            InputConverterType::HEX => ConversionResult::from(indexmap! {
+                OutputConverterType::DEC => "66".to_string(),
+                OutputConverterType::BIN => "1000010".to_string(),
+            }),
+        });
+        let outconvs = indexset![
+            OutputConverterType::DEC,
+            OutputConverterType::HEX,
+            OutputConverterType::BIN,
+            OutputConverterType::OCT,
+        ];
+
+        assert_eq!(
+            strip_colors(&output.grid(&outconvs, 80).to_string()),
+            concat!(
+                "             decimal  hexadecimal  binary\n",
+                "    decimal           2A           101010\n",
+                "hexadecimal  66                    1000010\n",
+            )
+        );
+        assert_eq!(
+            strip_colors(&output.grid(&outconvs, 40).to_string()),
+            concat!(
+                "             decimal  hexadecimal\n",
+                "    decimal           2A\n",
+                "hexadecimal  66\n",
+                "\n",
+                "             binary\n",
+                "    decimal  101010\n",
+                "hexadecimal  1000010\n",
+            )
+        );
+    }
+
+    #[test]
+    fn bit_diagram_ok() {
+        let diagram = BitDiagram {
+            inconv: InputConverterType::HEX,
+            width: 10,
+            bits: Ok(0x2A5),
+        };
+        assert_eq!(
+            strip_colors(&diagram.to_string()),
+            concat!(
+                "from hexadecimal:\n",
+                "    9   8    7   6   5   4   3   2   1   0\n",
+                "  +-------++---------------+---------------+\n",
+                "  | 1   0 || 1   0   1   0 | 0   1   0   1 |\n",
+                "  +-------++---------------+---------------+\n",
+                "      2            A               5\n",
+            )
+        );
+
+        let diagram = BitDiagram {
+            inconv: InputConverterType::DEC,
+            width: 4,
+            bits: Err(OutputError::DoesNotFit(4)),
+        };
+        assert_eq!(
+            strip_colors(&diagram.to_string()),
+            "from decimal:\n  skipped, value does not fit in 4 bits\n"
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use colored::{control::SHOULD_COLORIZE, Color, ColoredString, Colorize};
+use serde::{de, Deserialize};
+use std::{str::FromStr, sync::OnceLock};
+
+/// Theme in use, set once at startup
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Set the theme used by everything printed from now on
+///
+/// Has no effect after the theme has been used
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// Get the theme in use
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
+
+/// Styles of the text roles used in printing
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+#[serde(from = "ThemeConfig")]
+pub struct Theme {
+    pub heading: Style,
+    pub subheading: Style,
+    pub binary: Style,
+    pub value: Style,
+    pub error: Style,
+    pub note: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Preset::Default.theme()
+    }
+}
+
+/// Theme as written in the config file: a preset with optional per role overrides
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeConfig {
+    #[serde(default)]
+    preset: Preset,
+    heading: Option<Style>,
+    subheading: Option<Style>,
+    binary: Option<Style>,
+    value: Option<Style>,
+    error: Option<Style>,
+    note: Option<Style>,
+}
+
+impl From<ThemeConfig> for Theme {
+    fn from(value: ThemeConfig) -> Self {
+        let preset = value.preset.theme();
+        Self {
+            heading: value.heading.unwrap_or(preset.heading),
+            subheading: value.subheading.unwrap_or(preset.subheading),
+            binary: value.binary.unwrap_or(preset.binary),
+            value: value.value.unwrap_or(preset.value),
+            error: value.error.unwrap_or(preset.error),
+            note: value.note.unwrap_or(preset.note),
+        }
+    }
+}
+
+/// Built-in themes
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Preset {
+    /// Bright colours for dark backgrounds
+    #[default]
+    Default,
+    /// Dark colours for light backgrounds
+    Light,
+    /// No colours, only text attributes
+    Mono,
+}
+
+impl Preset {
+    /// Get the theme of this preset
+    pub fn theme(&self) -> Theme {
+        match self {
+            Self::Default => Theme {
+                heading: Style::color(Color::Green).bold(),
+                subheading: Style::default(),
+                binary: Style::color(Color::BrightWhite).bold(),
+                value: Style::color(Color::Blue).bold(),
+                error: Style::color(Color::Red).bold(),
+                note: Style::default().dimmed(),
+            },
+            Self::Light => Theme {
+                heading: Style::color(ThemeColor::from_ansi256(28)).bold(),
+                subheading: Style::default(),
+                binary: Style::color(Color::Black).bold(),
+                value: Style::color(ThemeColor::from_ansi256(19)).bold(),
+                error: Style::color(ThemeColor::from_ansi256(124)).bold(),
+                note: Style::default().dimmed(),
+            },
+            Self::Mono => Theme {
+                heading: Style::default().bold(),
+                subheading: Style::default(),
+                binary: Style::default().bold(),
+                value: Style::default().underline(),
+                error: Style::default().bold(),
+                note: Style::default().dimmed(),
+            },
+        }
+    }
+}
+
+impl FromStr for Preset {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "default" => Ok(Self::Default),
+            "light" => Ok(Self::Light),
+            "mono" => Ok(Self::Mono),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Preset {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| de::Error::custom(format!("Invalid theme preset: {}", s)))
+    }
+}
+
+/// Colour and attributes of a text role
+#[derive(Debug, PartialEq, Clone, Copy, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Style {
+    color: Option<ThemeColor>,
+    #[serde(default)]
+    bold: bool,
+    #[serde(default)]
+    italic: bool,
+    #[serde(default)]
+    underline: bool,
+    #[serde(default)]
+    dimmed: bool,
+}
+
+impl Style {
+    fn color(color: impl Into<ThemeColor>) -> Self {
+        Self {
+            color: Some(color.into()),
+            ..Default::default()
+        }
+    }
+
+    fn bold(self) -> Self {
+        Self { bold: true, ..self }
+    }
+
+    fn underline(self) -> Self {
+        Self {
+            underline: true,
+            ..self
+        }
+    }
+
+    fn dimmed(self) -> Self {
+        Self {
+            dimmed: true,
+            ..self
+        }
+    }
+
+    /// Same style without bold
+    pub fn nobold(self) -> Self {
+        Self {
+            bold: false,
+            ..self
+        }
+    }
+
+    /// Apply style to text
+    pub fn apply(&self, text: &str) -> ColoredString {
+        let mut res = match self.color {
+            Some(ThemeColor::Named(color)) => text.color(color),
+            // colored only writes basic and truecolour codes
+            Some(ThemeColor::Ansi256(index)) if SHOULD_COLORIZE.should_colorize() => {
+                format!("\x1b[38;5;{}m{}\x1b[0m", index, text).normal()
+            }
+            _ => text.normal(),
+        };
+        if self.bold {
+            res = res.bold();
+        }
+        if self.italic {
+            res = res.italic();
+        }
+        if self.underline {
+            res = res.underline();
+        }
+        if self.dimmed {
+            res = res.dimmed();
+        }
+        res
+    }
+}
+
+/// Colour of a text role
+///
+/// Accepts colour names ("green", "bright blue"), 256-colour indexes ("208")
+/// and truecolour values ("#RRGGBB")
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ThemeColor {
+    /// Basic colour or truecolour value
+    Named(Color),
+    /// Index in the 256-colour palette beyond the basic colours
+    Ansi256(u8),
+}
+
+impl From<Color> for ThemeColor {
+    fn from(value: Color) -> Self {
+        Self::Named(value)
+    }
+}
+
+impl ThemeColor {
+    /// Convert an index in the 256-colour palette
+    fn from_ansi256(index: u8) -> Self {
+        const BASIC: [Color; 16] = [
+            Color::Black,
+            Color::Red,
+            Color::Green,
+            Color::Yellow,
+            Color::Blue,
+            Color::Magenta,
+            Color::Cyan,
+            Color::White,
+            Color::BrightBlack,
+            Color::BrightRed,
+            Color::BrightGreen,
+            Color::BrightYellow,
+            Color::BrightBlue,
+            Color::BrightMagenta,
+            Color::BrightCyan,
+            Color::BrightWhite,
+        ];
+
+        match index {
+            // Basic colours are written with their own codes
+            0..=15 => Self::Named(BASIC[index as usize]),
+            _ => Self::Ansi256(index),
+        }
+    }
+}
+
+impl FromStr for ThemeColor {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Truecolour
+        if let Some(hex) = s.strip_prefix('#') {
+            if hex.len() != 6 || !hex.is_ascii() {
+                return Err(());
+            }
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| ());
+            return Ok(Self::Named(Color::TrueColor {
+                r: channel(0)?,
+                g: channel(2)?,
+                b: channel(4)?,
+            }));
+        }
+
+        // 256-colour palette
+        if let Ok(index) = s.parse::<u8>() {
+            return Ok(Self::from_ansi256(index));
+        }
+
+        // Named colour
+        s.replace('_', " ").parse().map(Self::Named)
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| de::Error::custom(format!("Invalid color: {}", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_color_ok() {
+        let tests = [
+            ("green", ThemeColor::Named(Color::Green)),
+            ("Bright Blue", ThemeColor::Named(Color::BrightBlue)),
+            ("bright_red", ThemeColor::Named(Color::BrightRed)),
+            ("9", ThemeColor::Named(Color::BrightRed)),
+            ("16", ThemeColor::Ansi256(16)),
+            ("208", ThemeColor::Ansi256(208)),
+            ("255", ThemeColor::Ansi256(255)),
+            (
+                "#1e90FF",
+                Color::TrueColor {
+                    r: 30,
+                    g: 144,
+                    b: 255,
+                }
+                .into(),
+            ),
+        ];
+        for (input, exp) in tests {
+            assert_eq!(input.parse::<ThemeColor>().unwrap(), exp);
+        }
+    }
+
+    #[test]
+    fn theme_color_err() {
+        let tests = ["", "greenish", "256", "#12345", "#12345G", "#ééé"];
+        for input in tests {
+            input.parse::<ThemeColor>().unwrap_err();
+        }
+    }
+
+    #[test]
+    fn theme_deserialize_ok() {
+        let theme: Theme = toml::from_str(
+            r##"
+            preset = "light"
+            value = { color = "#00AA00", italic = true }
+            "##,
+        )
+        .unwrap();
+
+        let exp = Theme {
+            value: Style {
+                color: Some(Color::TrueColor { r: 0, g: 170, b: 0 }.into()),
+                italic: true,
+                ..Default::default()
+            },
+            ..Preset::Light.theme()
+        };
+        assert_eq!(theme, exp);
+    }
+
+    #[test]
+    fn theme_deserialize_err() {
+        let tests = [
+            r#"preset = "neon""#,
+            r#"value = { color = "blu" }"#,
+            r#"value = { blink = true }"#,
+            r#"values = { bold = true }"#,
+        ];
+        for input in tests {
+            toml::from_str::<Theme>(input).unwrap_err();
+        }
+    }
+}